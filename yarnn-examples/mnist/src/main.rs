@@ -1,73 +1,88 @@
 use yarnn::prelude::*;
 use yarnn::native::{Native, NativeTensor};
 use yarnn_model_mnist::*;
-use yarnn::losses::CrossEntropyLoss;
+use yarnn::losses::BinaryCrossEntropyLoss;
+use yarnn::metrics::ConfusionMatrix;
+use yarnn::data::{DataLoader, DataLoaderConfig, LabeledDataset, one_hot};
+use yarnn::optimizer::Optimizer;
 use yarnn::optimizers::Adam;
+use yarnn::params::LayerGrads;
 use yarnn_native_blas::NativeBlas;
 use mnist::{Mnist, MnistBuilder};
 
-fn calc_accuracy<N, B: Backend<N>>(back: &B, pred: &B::Tensor, targets: &[u8]) -> f32 {
-    let mut vec = vec![0.0; pred.shape().size()];
-    back.store_tensor_f32(&pred, vec.as_mut_slice());
+/// Run with `--resume` to continue from the checkpoint written after every epoch
+fn main() {
+    const BATCH_SIZE: u32 = 64;
+    const EPOCHS: u32 = 4;
+    const CHECKPOINT_PATH: &str = "./mnist.checkpoint";
 
-    let mut positives = 0;
-    let mut total = 0;
+    let resume = std::env::args().skip(1).any(|arg| arg == "--resume");
 
-    for (x, &y) in vec.chunks(10).zip(targets.iter()) {
-        let x = &x[0 .. 10];
+    let backend: NativeBlas<f32, Native<_>> = Default::default();
+    let optimizer = Adam::default();
+    
+    // let mut model = MnistDenseModel::new(28, 28, 1);
+    let mut model = MnistConvModel::new(28, 28, 1);
+    model.init(&backend);
 
-        let mut max = 0;
-        let mut max_value = 0.0;
+    // the checkpoint file is the number of finished epochs (u32, little endian) followed by the model checkpoint
+    let mut start_epoch = 0;
 
-        for (idx, &i) in x.iter().enumerate() {
-            if i > max_value {
-                max_value = i;
-                max = idx as u8;
-            }
-        }
+    if resume {
+        let data = std::fs::read(CHECKPOINT_PATH).unwrap_or_else(|err| {
+            eprintln!("can't read {}: {}", CHECKPOINT_PATH, err);
+            std::process::exit(1);
+        });
 
-        if max == y {
-            positives += 1;
+        if data.len() < 4 {
+            eprintln!("{} is not a checkpoint", CHECKPOINT_PATH);
+            std::process::exit(1);
         }
 
-        total += 1;
-    }
-
-    (positives as f32) / (total as f32) 
-}
+        if let Err(err) = yarnn::serialize::load_checkpoint(&backend, &mut model, &optimizer, &data[4..]) {
+            eprintln!("can't restore {}: {}", CHECKPOINT_PATH, err);
+            std::process::exit(1);
+        }
 
-fn main() {
-    const BATCH_SIZE: usize = 64;
+        start_epoch = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
 
-    let backend: NativeBlas<f32, Native<_>> = Default::default();
-    let optimizer = Adam::default();
-    
-    // let mut model = MnistDenseModel::new(28, 28, 1);
-    let mut model = MnistConvModel::new(28, 28, 1);
-    model.init(&backend);
+        println!("resumed from {} after epoch {}", CHECKPOINT_PATH, start_epoch);
+    }
 
     println!("{}", &model);
 
     let mut train_ctx = Default::default();
     let mut test_ctx = Default::default();
 
-    let loss = CrossEntropyLoss::new();
+    // the model ends with a Sigmoid, every output is trained as an independent probability
+    let loss = BinaryCrossEntropyLoss::new(false);
 
     let Mnist { trn_img, trn_lbl, tst_img, tst_lbl, .. } = MnistBuilder::new()
         .base_path("./datasets/mnist")
         .label_format_digit()
         .finalize();
 
-    let mut inputs = NativeTensor::new((BATCH_SIZE as u32, 1, 28, 28));
-    let mut targets = NativeTensor::new((BATCH_SIZE as u32, 10));
-    let mut deltas = NativeTensor::new((BATCH_SIZE as u32, 10));
+    let train_set = LabeledDataset::new(&trn_img[..], (1, 28, 28), &trn_lbl[..], 10, 1.0 / 255.0);
+    let mut loader = DataLoader::new(&train_set, DataLoaderConfig {
+        batch_size: BATCH_SIZE,
+        shuffle: true,
+        ..Default::default()
+    });
+
+    // a resumed run shuffles like an uninterrupted one
+    loader.set_epoch(start_epoch as u64);
+
+    let mut inputs = NativeTensor::new(());
+    let mut targets = NativeTensor::new(());
+    let mut deltas = NativeTensor::new(());
 
     let test_count = 1000;
 
     let mut inputs0 = NativeTensor::new((test_count as u32, 1, 28, 28));
     let mut targets0 = NativeTensor::new((test_count as u32, 10));
+    let mut losses0 = NativeTensor::new((test_count as u32, 10));
 
-    let mut tmp = vec![0u8; 10 * test_count];
+    let mut tmp = vec![0.0; 10 * test_count];
 
     let inputs0_slice = &tst_img[0..test_count * 784];
     let targets0_slice = &tst_lbl[0..test_count];
@@ -75,40 +90,42 @@ fn main() {
     backend.load_tensor_u8(&mut inputs0, inputs0_slice);
     backend.scale(&mut inputs0, 1.0 / 255.0);
 
-    for (idx, &t) in targets0_slice.iter().enumerate() {
-        tmp[idx * 10 + t as usize] = 1;
-    }
-
-    backend.load_tensor_u8(&mut targets0, &tmp[..]);
+    one_hot(targets0_slice, 10, &mut tmp);
+    backend.load_tensor_f32(&mut targets0, &tmp[..]);
 
-    for epoch in 1 ..= 4 {
+    for epoch in start_epoch + 1 ..= EPOCHS {
         println!("epoch {}", epoch);
 
-        for step in 0 .. (60000 / BATCH_SIZE) {
-            let offset = step * BATCH_SIZE;
-            let mut tmp = [0u8; 10 * BATCH_SIZE];
-
-            let inputs_slice = &trn_img[offset * 784 .. (offset + BATCH_SIZE) * 784 ];
-            let targets_slice = &trn_lbl[offset..offset + BATCH_SIZE];
+        let mut batches = loader.epoch();
 
-            backend.load_tensor_u8(&mut inputs, inputs_slice);
-            backend.scale(&mut inputs, 1.0 / 255.0);
-
-            for (idx, &t) in targets_slice.iter().enumerate() {
-                tmp[idx * 10 + t as usize] = 1;
+        while batches.next_batch(&backend, &mut inputs, &mut targets).is_some() {
+            if deltas.shape() != targets.shape() {
+                deltas.resize(targets.shape().clone());
             }
 
-            backend.load_tensor_u8(&mut targets, &tmp[..]);
-
             model.forward(&backend, &inputs, &mut train_ctx);
             loss.derivative(&backend, &mut deltas, train_ctx.outputs(), &targets);
             model.backward(&backend, &deltas, &inputs, &mut train_ctx);            
             model.calc_gradients(&backend, &deltas, &inputs, &mut train_ctx);
+            optimizer.prepare(&backend, &mut LayerGrads::new(&mut model));
             model.optimize(&backend, &optimizer);
         }
 
         model.forward(&backend, &inputs0, &mut test_ctx);
 
-        println!("Accuracy {}", calc_accuracy(&backend, test_ctx.outputs(), targets0_slice));
+        let test_loss = loss.batch_loss(&backend, &mut losses0, test_ctx.outputs(), &targets0);
+
+        println!("Loss {}", test_loss);
+        let matrix = ConfusionMatrix::from_tensor(&backend, test_ctx.outputs(), targets0_slice);
+
+        println!("Accuracy {}", matrix.accuracy());
+        println!("Macro F1 {}", matrix.macro_f1());
+
+        let mut checkpoint = epoch.to_le_bytes().to_vec();
+        checkpoint.extend(yarnn::serialize::save_checkpoint(&backend, &mut model, &optimizer));
+        std::fs::write(CHECKPOINT_PATH, checkpoint).expect("failed to write checkpoint");
     }
+
+    let weights = yarnn::serialize::save(&backend, &mut model);
+    std::fs::write("./mnist.weights", weights).expect("failed to write weights");
 }
@@ -60,3 +60,52 @@ mod conv {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yarnn::losses::BinaryCrossEntropyLoss;
+    use yarnn::native::Native;
+    use yarnn::network::Network;
+    use yarnn::optimizers::Adam;
+
+    type B = Native<f32>;
+
+    #[test]
+    fn test_conv_model_train() {
+        const SIZE: usize = 16;
+
+        // four classes, each one lights up its own quadrant of the image
+        let labels: Vec<u8> = (0 .. 32).map(|i| i % 4).collect();
+        let mut x = vec![0.0; labels.len() * SIZE * SIZE];
+        let mut y = vec![0.0; labels.len() * 10];
+
+        for (i, &label) in labels.iter().enumerate() {
+            let (top, left) = ((label as usize / 2) * SIZE / 2, (label as usize % 2) * SIZE / 2);
+
+            for row in top .. top + SIZE / 2 {
+                for col in left .. left + SIZE / 2 {
+                    x[i * SIZE * SIZE + row * SIZE + col] = 1.0;
+                }
+            }
+
+            y[i * 10 + label as usize] = 1.0;
+        }
+
+        let model = MnistConvModel::<f32, B, Adam<f32, B>>::new(SIZE as u32, SIZE as u32, 1);
+        let mut net = Network::new(Native::default(), Adam::default(), BinaryCrossEntropyLoss::new(false), model);
+
+        net.init();
+        net.train(&x, &y, 8, 50);
+
+        assert_eq!(net.confusion_matrix(&x, &labels).accuracy(), 1.0);
+
+        // the Sigmoid outputs are independent, only the target one may stay high
+        let mut pred = vec![0.0; y.len()];
+        net.predict(&x, &mut pred);
+
+        for (p, t) in pred.iter().zip(y.iter()) {
+            assert!((p - t).abs() < 0.5, "{:?}", pred);
+        }
+    }
+}
@@ -1,28 +1,29 @@
-#[allow(dead_code)]
-fn img_to_col_get_pixel(img: &[f32], img_rows: usize, img_cols: usize, 
+use yarnn::native::NativeNumber;
+
+fn img_to_col_get_pixel<N: NativeNumber>(img: &[N], img_rows: usize, img_cols: usize, 
                         mut row: isize, mut col: isize, channel: usize, 
-                        pad_row: usize, pad_col: usize) -> f32 
+                        pad_row: usize, pad_col: usize) -> N 
 {
     row -= pad_row as isize;
     col -= pad_col as isize;
     
     if row < 0 || col < 0 ||
        row >= img_rows as isize || 
-       col >= img_cols as isize { return 0.0 }
+       col >= img_cols as isize { return N::ZERO }
        
     img[(channel * img_rows + row as usize) * img_cols + col as usize]
 }
 
-#[allow(dead_code)]
-pub fn img_to_col(col: &mut [f32], img: &[f32], channels: usize,
+/// Unfolds the `channels x img_rows x img_cols` image into a `(channels * k_rows * k_cols) x (col_rows * col_cols)`
+/// matrix, one column per output pixel holding its patch in the filter layout.
+/// `pad_row` and `pad_col` are the top and left paddings, pixels outside the image are zero.
+pub fn img_to_col<N: NativeNumber>(col: &mut [N], img: &[N], channels: usize,
               k_rows: usize, k_cols: usize,
               img_rows: usize, img_cols: usize, 
+              col_rows: usize, col_cols: usize, 
               s_row: usize, s_col: usize, 
               pad_row: usize, pad_col: usize) 
 {
-    let col_rows = (img_rows + 2 * pad_row - k_rows) / s_row + 1;
-    let col_cols = (img_cols + 2 * pad_col - k_cols) / s_col + 1;
-    
     let k_size = k_rows * k_cols;
     let channels_col = channels * k_size;
     
@@ -31,18 +32,17 @@ pub fn img_to_col(col: &mut [f32], img: &[f32], channels: usize,
     let col_s = &mut col[0..col_size];
     
     for ch in 0..channels_col {
-        let offset_ch = ch / k_rows / k_cols;
-        let offset_row = (ch / k_rows) % k_cols;
-        let offset_col = ch % k_rows;
+        let offset_ch = ch / k_size;
+        let index_col = ch % k_size;
+        let offset_row = index_col / k_cols;
+        let offset_col = index_col % k_cols;
         
         for row in 0..col_rows {
             for col in 0..col_cols {
                 let img_row = row * s_row + offset_row;
                 let img_col = col * s_col + offset_col;
                 
-                let index_row = row * col_cols + col;
-                let index_col = offset_row * k_rows + offset_col;
-                let index = offset_ch * (k_size * out_size) + index_row * k_size + index_col;
+                let index = ch * out_size + row * col_cols + col;
                 
                 col_s[index] = img_to_col_get_pixel(
                     img, img_rows, img_cols, 
@@ -54,9 +54,9 @@ pub fn img_to_col(col: &mut [f32], img: &[f32], channels: usize,
     }
 }
 
-fn col_to_img_add_pixel(img: &mut [f32], img_rows: usize, img_cols: usize, 
+fn col_to_img_add_pixel<N: NativeNumber>(img: &mut [N], img_rows: usize, img_cols: usize, 
                         mut row: isize, mut col: isize, channel: usize, 
-                        pad_row: usize, pad_col: usize, val: f32) {
+                        pad_row: usize, pad_col: usize, val: N) {
                         
     row -= pad_row as isize;
     col -= pad_col as isize;
@@ -68,16 +68,15 @@ fn col_to_img_add_pixel(img: &mut [f32], img_rows: usize, img_cols: usize,
     img[(channel * img_rows + row as usize) * img_cols + col as usize] += val;
 }
 
-#[allow(dead_code)]
-pub fn col_to_img(img: &mut [f32], col: &[f32], channels: usize,
+/// Folds patches laid out like `img_to_col` produces back into the image, summing overlaps.
+/// Values falling into the padding are dropped.
+pub fn col_to_img<N: NativeNumber>(img: &mut [N], col: &[N], channels: usize,
               k_rows: usize, k_cols: usize,
               img_rows: usize, img_cols: usize, 
+              col_rows: usize, col_cols: usize, 
               s_row: usize, s_col: usize, 
               pad_row: usize, pad_col: usize) {
 
-    let col_rows = (img_rows + 2 * pad_row - k_rows) / s_row + 1;
-    let col_cols = (img_cols + 2 * pad_col - k_cols) / s_col + 1;
-    
     let k_size = k_rows * k_cols;
     let channels_col = channels * k_size;
     
@@ -87,18 +86,17 @@ pub fn col_to_img(img: &mut [f32], col: &[f32], channels: usize,
     let col_s = &col[0..col_size];
     
     for ch in 0..channels_col {
-        let offset_ch = ch / k_rows / k_cols;
-        let offset_row = (ch / k_rows) % k_cols;
-        let offset_col = ch % k_rows;
+        let offset_ch = ch / k_size;
+        let index_col = ch % k_size;
+        let offset_row = index_col / k_cols;
+        let offset_col = index_col % k_cols;
         
         for row in 0..col_rows {
             for col in 0..col_cols {
                 let img_row = row * s_row + offset_row;
                 let img_col = col * s_col + offset_col;
                 
-                let index_row = row * col_cols + col;
-                let index_col = offset_row * k_rows + offset_col;
-                let index = offset_ch * (k_size * out_size) + index_row * k_size + index_col;
+                let index = ch * out_size + row * col_cols + col;
                 
                 col_to_img_add_pixel(
                     img, img_rows, img_cols, 
@@ -131,17 +129,28 @@ mod tests {
         
         let mut col = vec![0.0; 72];
 
-        img_to_col(&mut col, img, 2, 3, 3, 4, 4, 1, 1, 0, 0);
+        img_to_col(&mut col, img, 2, 3, 3, 4, 4, 2, 2, 1, 1, 0, 0);
         
         let tmp: &[f32] = &[
-            1.0,  2.0,  3.0,  5.0,  6.0,  7.0,  9.0, 10.0, 11.0,
-            2.0,  3.0,  4.0,  6.0,  7.0,  8.0, 10.0, 11.0, 12.0,
-            5.0,  6.0,  7.0,  9.0, 10.0, 11.0, 13.0, 14.0, 15.0,
-            6.0,  7.0,  8.0, 10.0, 11.0, 12.0, 14.0, 15.0, 16.0,
-            1.5,  2.5,  3.5,  5.5,  6.5,  7.5,  9.5, 10.5, 11.5,
-            2.5,  3.5,  4.5,  6.5,  7.5,  8.5, 10.5, 11.5, 12.5,
-            5.5,  6.5,  7.5,  9.5, 10.5, 11.5, 13.5, 14.5, 15.5,
-            6.5,  7.5,  8.5, 10.5, 11.5, 12.5, 14.5, 15.5, 16.5,
+             1.0,  2.0,  5.0,  6.0,
+             2.0,  3.0,  6.0,  7.0,
+             3.0,  4.0,  7.0,  8.0,
+             5.0,  6.0,  9.0, 10.0,
+             6.0,  7.0, 10.0, 11.0,
+             7.0,  8.0, 11.0, 12.0,
+             9.0, 10.0, 13.0, 14.0,
+            10.0, 11.0, 14.0, 15.0,
+            11.0, 12.0, 15.0, 16.0,
+
+             1.5,  2.5,  5.5,  6.5,
+             2.5,  3.5,  6.5,  7.5,
+             3.5,  4.5,  7.5,  8.5,
+             5.5,  6.5,  9.5, 10.5,
+             6.5,  7.5, 10.5, 11.5,
+             7.5,  8.5, 11.5, 12.5,
+             9.5, 10.5, 13.5, 14.5,
+            10.5, 11.5, 14.5, 15.5,
+            11.5, 12.5, 15.5, 16.5,
         ];
         
         assert_eq!(
@@ -153,14 +162,25 @@ mod tests {
     #[test]
     fn test_col_to_img() {
         let col: &[f32] = &[
-            1.0,  2.0,  3.0,  5.0,  6.0,  7.0,  9.0, 10.0, 11.0,
-            2.0,  3.0,  4.0,  6.0,  7.0,  8.0, 10.0, 11.0, 12.0,
-            5.0,  6.0,  7.0,  9.0, 10.0, 11.0, 13.0, 14.0, 15.0,
-            6.0,  7.0,  8.0, 10.0, 11.0, 12.0, 14.0, 15.0, 16.0,
-            1.5,  2.5,  3.5,  5.5,  6.5,  7.5,  9.5, 10.5, 11.5,
-            2.5,  3.5,  4.5,  6.5,  7.5,  8.5, 10.5, 11.5, 12.5,
-            5.5,  6.5,  7.5,  9.5, 10.5, 11.5, 13.5, 14.5, 15.5,
-            6.5,  7.5,  8.5, 10.5, 11.5, 12.5, 14.5, 15.5, 16.5,
+             1.0,  2.0,  5.0,  6.0,
+             2.0,  3.0,  6.0,  7.0,
+             3.0,  4.0,  7.0,  8.0,
+             5.0,  6.0,  9.0, 10.0,
+             6.0,  7.0, 10.0, 11.0,
+             7.0,  8.0, 11.0, 12.0,
+             9.0, 10.0, 13.0, 14.0,
+            10.0, 11.0, 14.0, 15.0,
+            11.0, 12.0, 15.0, 16.0,
+
+             1.5,  2.5,  5.5,  6.5,
+             2.5,  3.5,  6.5,  7.5,
+             3.5,  4.5,  7.5,  8.5,
+             5.5,  6.5,  9.5, 10.5,
+             6.5,  7.5, 10.5, 11.5,
+             7.5,  8.5, 11.5, 12.5,
+             9.5, 10.5, 13.5, 14.5,
+            10.5, 11.5, 14.5, 15.5,
+            11.5, 12.5, 15.5, 16.5,
         ];
 
         let y: &mut [f32] = &mut [
@@ -175,7 +195,7 @@ mod tests {
             0.0, 0.0, 0.0, 0.0,
         ];
         
-        col_to_img(y, col, 2, 3, 3, 4, 4, 1, 1, 0, 0);
+        col_to_img(y, col, 2, 3, 3, 4, 4, 2, 2, 1, 1, 0, 0);
 
         let tmp: &[f32] = &[
              1.0,  4.0,  6.0,  4.0, 
@@ -1,5 +1,6 @@
 mod img2col;
 
+use self::img2col::*;
 use yarnn::backend::*;
 use yarnn::native::*;
 use yarnn::tensor::*;
@@ -7,6 +8,47 @@ use std::marker::PhantomData;
 
 extern crate openblas_src;
 
+/// `NativeNumber` with a BLAS implementation, `sgemm` and friends for `f32`, `dgemm` and friends for `f64`
+pub trait BlasNumber: NativeNumber {
+    /// # Safety
+    /// The slices have to hold every element addressed through the sizes and leading dimensions
+    unsafe fn gemm(transa: u8, transb: u8, m: i32, n: i32, k: i32, alpha: Self, a: &[Self], lda: i32,
+                   b: &[Self], ldb: i32, beta: Self, c: &mut [Self], ldc: i32);
+
+    /// # Safety
+    /// `x` and `y` have to hold `n` elements at their increments
+    unsafe fn axpy(n: i32, alpha: Self, x: &[Self], incx: i32, y: &mut [Self], incy: i32);
+
+    /// # Safety
+    /// `x` has to hold `n` elements at its increment
+    unsafe fn scal(n: i32, alpha: Self, x: &mut [Self], incx: i32);
+}
+
+macro_rules! impl_blas_number {
+    ($t:ident, $gemm:ident, $axpy:ident, $scal:ident) => {
+        impl BlasNumber for $t {
+            #[inline]
+            unsafe fn gemm(transa: u8, transb: u8, m: i32, n: i32, k: i32, alpha: Self, a: &[Self], lda: i32,
+                           b: &[Self], ldb: i32, beta: Self, c: &mut [Self], ldc: i32) {
+                blas::$gemm(transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+            }
+
+            #[inline]
+            unsafe fn axpy(n: i32, alpha: Self, x: &[Self], incx: i32, y: &mut [Self], incy: i32) {
+                blas::$axpy(n, alpha, x, incx, y, incy)
+            }
+
+            #[inline]
+            unsafe fn scal(n: i32, alpha: Self, x: &mut [Self], incx: i32) {
+                blas::$scal(n, alpha, x, incx)
+            }
+        }
+    };
+}
+
+impl_blas_number!(f32, sgemm, saxpy, sscal);
+impl_blas_number!(f64, dgemm, daxpy, dscal);
+
 pub struct NativeBlas<N, B> 
     where N: NativeNumber,
           B: NativeBackend<N>
@@ -80,6 +122,16 @@ impl<N, B> Backend<N> for NativeBlas<N, B>
         N::from_f32(val)
     }
 
+    #[inline]
+    fn scalar_f64(&self, val: f64) -> N {
+        N::from_f64(val)
+    }
+
+    #[inline]
+    fn scalar_to_f64(&self, val: N) -> f64 {
+        val.to_f64()
+    }
+
     #[inline]
     fn fill_scalar(&self, t: &mut Self::Tensor, scalar: N) {
         self.inner.fill_scalar(t, scalar)
@@ -96,8 +148,9 @@ impl<N, B> Backend<N> for NativeBlas<N, B>
     }
 }
 
-impl<B> BackendGemm<f32> for NativeBlas<f32, B> 
-    where B: NativeBackend<f32>
+impl<N, B> BackendGemm<N> for NativeBlas<N, B> 
+    where N: BlasNumber,
+          B: NativeBackend<N>
 {
     #[inline]
     fn matmul(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
@@ -116,12 +169,12 @@ impl<B> BackendGemm<f32> for NativeBlas<f32, B>
         let k = b_shape.get(0) as i32;
         
         unsafe {
-            blas::sgemm('N' as u8, 'N' as u8,
+            N::gemm('N' as u8, 'N' as u8,
                 n, m, k, 
-                1.0, 
+                N::ONE, 
                 self.read_tensor(b), n, 
                 self.read_tensor(a), k, 
-                0.0, 
+                N::ZERO, 
                 self.write_tensor(dst), n);
         }
     }
@@ -143,12 +196,12 @@ impl<B> BackendGemm<f32> for NativeBlas<f32, B>
         let k = b_shape.get(1) as i32;
         
         unsafe {
-            blas::sgemm('T' as u8, 'N' as u8,
+            N::gemm('T' as u8, 'N' as u8,
                 n, m, k, 
-                1.0, 
+                N::ONE, 
                 self.read_tensor(b), k, 
                 self.read_tensor(a), k, 
-                0.0, 
+                N::ZERO, 
                 self.write_tensor(dst), n);
         }
     }
@@ -170,33 +223,56 @@ impl<B> BackendGemm<f32> for NativeBlas<f32, B>
         let k = b_shape.get(0) as i32;
         
         unsafe {
-            blas::sgemm('N' as u8, 'T' as u8,
+            N::gemm('N' as u8, 'T' as u8,
                 n, m, k, 
-                1.0, 
+                N::ONE, 
                 self.read_tensor(b), n, 
                 self.read_tensor(a), m, 
-                0.0, 
+                N::ZERO, 
                 self.write_tensor(dst), n);
         }
     }
 
     #[inline]
-    fn matmul_tt(&self, _dst: &mut Self::Tensor, _a: &Self::Tensor, _b: &Self::Tensor) {
-        unimplemented!();
+    fn matmul_tt(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
+        let a_shape = a.shape();
+        let b_shape = b.shape();
+        let c_shape = dst.shape().clone();
+
+        assert_eq!(a_shape.get(1), c_shape.get(0));
+        assert_eq!(b_shape.get(0), c_shape.get(1));
+
+        assert_eq!(a_shape.dims, 2);
+        assert_eq!(b_shape.dims, 2);
+
+        let m = a_shape.get(1) as i32;
+        let n = b_shape.get(0) as i32;
+        let k = b_shape.get(1) as i32;
+        
+        unsafe {
+            N::gemm(b'T', b'T',
+                n, m, k, 
+                N::ONE, 
+                self.read_tensor(b), k, 
+                self.read_tensor(a), m, 
+                N::ZERO, 
+                self.write_tensor(dst), n);
+        }
     }
 }
 
-impl<B> BackendAxpy<f32> for NativeBlas<f32, B> 
-    where B: NativeBackend<f32>
+impl<N, B> BackendAxpy<N> for NativeBlas<N, B> 
+    where N: BlasNumber,
+          B: NativeBackend<N>
 {
     #[inline]
-    fn axpy(&self, dst: &mut Self::Tensor, scale: f32, x: &Self::Tensor) {
+    fn axpy(&self, dst: &mut Self::Tensor, scale: N, x: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
         assert!(x.shape() == dst.shape());
 
         unsafe {
-            blas::saxpy(
+            N::axpy(
                 dst_size as i32,
                 scale,
                 self.read_tensor(x),
@@ -208,15 +284,16 @@ impl<B> BackendAxpy<f32> for NativeBlas<f32, B>
     }
 }
 
-impl<B> BackendScale<f32> for NativeBlas<f32, B> 
-    where B: NativeBackend<f32>
+impl<N, B> BackendScale<N> for NativeBlas<N, B> 
+    where N: BlasNumber,
+          B: NativeBackend<N>
 {
     #[inline]
-    fn scale(&self, dst: &mut Self::Tensor, scale: f32) {
+    fn scale(&self, dst: &mut Self::Tensor, scale: N) {
         let dst_size = dst.shape().size();
 
         unsafe {
-            blas::sscal(
+            N::scal(
                 dst_size as i32,
                 scale,
                 self.write_tensor(dst),
@@ -227,76 +304,76 @@ impl<B> BackendScale<f32> for NativeBlas<f32, B>
 }
 
 
-impl<B: NativeBackend<f32> + BackendSigmoid<f32>> BackendSigmoid<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendActivation<N>> BackendActivation<N> for NativeBlas<N, B> {
     #[inline]
-    fn sigmoid(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
-        self.inner.sigmoid(dst, data)
+    fn activation(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor) {
+        self.inner.activation(act, dst, x)
     }
 
     #[inline]
-    fn sigmoid_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
-        self.inner.sigmoid_grad(dst, z, d)
+    fn activation_grad(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
+        self.inner.activation_grad(act, dst, x, y, dy)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendReLu<f32>> BackendReLu<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendBias<N>> BackendBias<N> for NativeBlas<N, B> {
     #[inline]
-    fn relu(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
-        self.inner.relu(dst, data)
+    fn bias_add(&self, dst: &mut Self::Tensor, biases: &Self::Tensor) {
+        self.inner.bias_add(dst, biases)
     }
 
     #[inline]
-    fn relu_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
-        self.inner.relu_grad(dst, z, d)
+    fn bias_grad(&self, dbiases: &mut Self::Tensor, deltas: &Self::Tensor) {
+        self.inner.bias_grad(dbiases, deltas)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendBias<f32>> BackendBias<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendBias2d<N>> BackendBias2d<N> for NativeBlas<N, B> {
     #[inline]
-    fn bias_add(&self, dst: &mut Self::Tensor, biases: &Self::Tensor) {
-        self.inner.bias_add(dst, biases)
+    fn bias_add2d(&self, dst: &mut Self::Tensor, biases: &Self::Tensor) {
+        self.inner.bias_add2d(dst, biases)
     }
 
     #[inline]
-    fn bias_grad(&self, dbiases: &mut Self::Tensor, deltas: &Self::Tensor) {
-        self.inner.bias_grad(dbiases, deltas)
+    fn bias_grad2d(&self, dbiases: &mut Self::Tensor, deltas: &Self::Tensor) {
+        self.inner.bias_grad2d(dbiases, deltas)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendMse<f32>> BackendMse<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendMse<N>> BackendMse<N> for NativeBlas<N, B> {
     #[inline]
-    fn scaled_square_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: f32) {
+    fn scaled_square_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: N) {
         self.inner.scaled_square_diff(dst, a, b, scale)
     }
 
     #[inline]
-    fn scaled_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: f32) {
+    fn scaled_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: N) {
         self.inner.scaled_diff(dst, a, b, scale)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendAxpys<f32>> BackendAxpys<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendAxpys<N>> BackendAxpys<N> for NativeBlas<N, B> {
     #[inline]
-    fn axpys(&self, dst: &mut Self::Tensor, scale: f32, a: &Self::Tensor) {
+    fn axpys(&self, dst: &mut Self::Tensor, scale: N, a: &Self::Tensor) {
         self.inner.axpys(dst, scale, a)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendAdd<f32>> BackendAdd<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendAdd<N>> BackendAdd<N> for NativeBlas<N, B> {
     #[inline]
     fn add(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         self.inner.add(dst, a)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendSub<f32>> BackendSub<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendSub<N>> BackendSub<N> for NativeBlas<N, B> {
     #[inline]
     fn sub(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
         self.inner.sub(dst, a, b)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendMul<f32>> BackendMul<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendMul<N>> BackendMul<N> for NativeBlas<N, B> {
     #[inline]
     fn mul(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         self.inner.mul(dst, a)
@@ -304,14 +381,14 @@ impl<B: NativeBackend<f32> + BackendMul<f32>> BackendMul<f32> for NativeBlas<f32
 }
 
 
-impl<B: NativeBackend<f32> + BackendCopy<f32>> BackendCopy<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendCopy<N>> BackendCopy<N> for NativeBlas<N, B> {
     #[inline]
     fn copy(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         self.inner.copy(dst, a)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendMaximum<f32>> BackendMaximum<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendMaximum<N>> BackendMaximum<N> for NativeBlas<N, B> {
     #[inline]
     fn maximum(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         self.inner.maximum(dst, a)
@@ -319,40 +396,362 @@ impl<B: NativeBackend<f32> + BackendMaximum<f32>> BackendMaximum<f32> for Native
 }
 
 
-impl<B: NativeBackend<f32> + BackendAdam<f32>> BackendAdam<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendAdam<N>> BackendAdam<N> for NativeBlas<N, B> {
     #[inline]
-    fn adam_p(&self, dst: &mut Self::Tensor, lr: f32, moms: &Self::Tensor, vels: &Self::Tensor, eps: f32) {
+    fn adam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, vels: &Self::Tensor, eps: N) {
         self.inner.adam_p(dst, lr, moms, vels, eps)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendSoftmax<f32>> BackendSoftmax<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendAdaDelta<N>> BackendAdaDelta<N> for NativeBlas<N, B> {
+    #[inline]
+    fn adadelta_p(&self, dst: &mut Self::Tensor, lr: N, grads: &Self::Tensor, accum: &Self::Tensor, delta_accum: &mut Self::Tensor, rho: N, eps: N) {
+        self.inner.adadelta_p(dst, lr, grads, accum, delta_accum, rho, eps)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendNadam<N>> BackendNadam<N> for NativeBlas<N, B> {
+    #[inline]
+    fn nadam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, grads: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, grad_coef: N, eps: N) {
+        self.inner.nadam_p(dst, lr, moms, grads, vels, mom_coef, grad_coef, eps)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendLamb<N>> BackendLamb<N> for NativeBlas<N, B> {
+    #[inline]
+    fn lamb_update(&self, update: &mut Self::Tensor, params: &Self::Tensor, moms: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, vel_coef: N, eps: N, weight_decay: N) {
+        self.inner.lamb_update(update, params, moms, vels, mom_coef, vel_coef, eps, weight_decay)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendSoftmax<N>> BackendSoftmax<N> for NativeBlas<N, B> {
     #[inline]
     fn softmax(&self, y: &mut Self::Tensor, x: &Self::Tensor) {
         self.inner.softmax(y, x)
     }
+
+    #[inline]
+    fn softmax_grad(&self, dx: &mut Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
+        self.inner.softmax_grad(dx, y, dy)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendCrossEntropy<N>> BackendCrossEntropy<N> for NativeBlas<N, B> {
+    #[inline]
+    fn cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.cross_entropy(dst, pred, target)
+    }
+
+    #[inline]
+    fn cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.cross_entropy_grad(dst, pred, target)
+    }
+
+    #[inline]
+    fn softmax_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.softmax_cross_entropy(dst, logits, target)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendBinaryCrossEntropy<N>> BackendBinaryCrossEntropy<N> for NativeBlas<N, B> {
+    #[inline]
+    fn binary_cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.binary_cross_entropy(dst, pred, target)
+    }
+
+    #[inline]
+    fn binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.binary_cross_entropy_grad(dst, pred, target)
+    }
+
+    #[inline]
+    fn sigmoid_binary_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.sigmoid_binary_cross_entropy(dst, logits, target)
+    }
+
+    #[inline]
+    fn sigmoid_binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.sigmoid_binary_cross_entropy_grad(dst, logits, target)
+    }
 }
 
-impl<B: NativeBackend<f32> + BackendConv2d<f32>> BackendConv2d<f32> for NativeBlas<f32, B> {
-    type Context = ();
+impl<N: BlasNumber, B: NativeBackend<N> + BackendHuber<N>> BackendHuber<N> for NativeBlas<N, B> {
+    #[inline]
+    fn huber(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        self.inner.huber(dst, pred, target, delta)
+    }
 
     #[inline]
-    fn conv2d_forward(&self, y: &mut Self::Tensor, x: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
-        self.inner.conv2d_forward(y, x, w, conv_info)
+    fn huber_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        self.inner.huber_grad(dst, pred, target, delta)
     }
+}
 
+impl<N: BlasNumber, B: NativeBackend<N> + BackendHinge<N>> BackendHinge<N> for NativeBlas<N, B> {
     #[inline]
-    fn conv2d_backward_input(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
-       self.inner.conv2d_backward_input(dx, dy, w, conv_info)
+    fn hinge(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.hinge(dst, pred, target)
     }
 
     #[inline]
-    fn conv2d_backward_filter(&self, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
-        self.inner.conv2d_backward_filter(dw, x, dy, conv_info)
+    fn hinge_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.hinge_grad(dst, pred, target)
     }
 }
 
-impl<B: NativeBackend<f32> + BackendMaxPool2d<f32>> BackendMaxPool2d<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendKlDivergence<N>> BackendKlDivergence<N> for NativeBlas<N, B> {
+    #[inline]
+    fn kl_divergence(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.kl_divergence(dst, pred, target)
+    }
+
+    #[inline]
+    fn kl_divergence_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.kl_divergence_grad(dst, pred, target)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendNll<N>> BackendNll<N> for NativeBlas<N, B> {
+    #[inline]
+    fn nll(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.nll(dst, log_probs, target)
+    }
+
+    #[inline]
+    fn nll_grad(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        self.inner.nll_grad(dst, log_probs, target)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendReduce<N>> BackendReduce<N> for NativeBlas<N, B> {
+    #[inline]
+    fn sum(&self, t: &Self::Tensor) -> N {
+        self.inner.sum(t)
+    }
+
+    #[inline]
+    fn norm_l2(&self, t: &Self::Tensor) -> N {
+        self.inner.norm_l2(t)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendClamp<N>> BackendClamp<N> for NativeBlas<N, B> {
+    #[inline]
+    fn clamp(&self, t: &mut Self::Tensor, min: N, max: N) {
+        self.inner.clamp(t, min, max)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendDropout<N>> BackendDropout<N> for NativeBlas<N, B> {
+    #[inline]
+    fn dropout(&self, y: &mut Self::Tensor, mask: &mut Self::Tensor, x: &Self::Tensor, rate: N, seed: u64) {
+        self.inner.dropout(y, mask, x, rate, seed)
+    }
+
+    #[inline]
+    fn dropout_grad(&self, dx: &mut Self::Tensor, mask: &Self::Tensor, dy: &Self::Tensor) {
+        self.inner.dropout_grad(dx, mask, dy)
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendBatchNorm<N>> BackendBatchNorm<N> for NativeBlas<N, B> {
+    #[inline]
+    fn batch_norm_stats(&self, mean: &mut Self::Tensor, var: &mut Self::Tensor, x: &Self::Tensor) {
+        self.inner.batch_norm_stats(mean, var, x)
+    }
+
+    #[inline]
+    fn batch_norm(&self, y: &mut Self::Tensor, xhat: &mut Self::Tensor, x: &Self::Tensor,
+                  mean: &Self::Tensor, var: &Self::Tensor, gamma: &Self::Tensor, beta: &Self::Tensor, eps: N) {
+        self.inner.batch_norm(y, xhat, x, mean, var, gamma, beta, eps)
+    }
+
+    #[inline]
+    fn batch_norm_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor,
+                       var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        self.inner.batch_norm_grad(dx, dy, xhat, var, gamma, eps)
+    }
+
+    #[inline]
+    fn batch_norm_inference_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor,
+                                 var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        self.inner.batch_norm_inference_grad(dx, dy, var, gamma, eps)
+    }
+
+    #[inline]
+    fn batch_norm_param_grads(&self, dgamma: &mut Self::Tensor, dbeta: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor) {
+        self.inner.batch_norm_param_grads(dgamma, dbeta, dy, xhat)
+    }
+}
+
+/// im2col scratch buffer of a convolution layer, grown to the largest image it has seen
+#[derive(Default)]
+pub struct NativeBlasConv2dContext<N> {
+    col: Vec<N>,
+}
+
+impl<N: NativeNumber> NativeBlasConv2dContext<N> {
+    fn col(&mut self, size: usize) -> &mut [N] {
+        if self.col.len() < size {
+            self.col.resize(size, N::ZERO);
+        }
+
+        &mut self.col[0..size]
+    }
+}
+
+/// Row-major `c = alpha * op(a) * op(b) + beta * c` with the column-major BLAS `?gemm`,
+/// computed as `c^T = op(b)^T * op(a)^T`
+#[inline]
+fn gemm<N: BlasNumber>(trans_a: bool, trans_b: bool, m: usize, n: usize, k: usize, alpha: N,
+                       a: &[N], lda: usize, b: &[N], ldb: usize, beta: N, c: &mut [N], ldc: usize) {
+    let trans = |t: bool| if t { b'T' } else { b'N' };
+
+    unsafe {
+        N::gemm(trans(trans_b), trans(trans_a),
+            n as i32, m as i32, k as i32,
+            alpha,
+            b, ldb as i32,
+            a, lda as i32,
+            beta,
+            c, ldc as i32);
+    }
+}
+
+/// Every image of the batch is unfolded by `img_to_col` into a `(channels * k_rows * k_cols) x (out_rows * out_cols)`
+/// matrix. Filters laid out as `(filters, channels * k_rows * k_cols)` make each pass a single `gemm` per image.
+impl<N: BlasNumber, B: NativeBackend<N>> BackendConv2d<N> for NativeBlas<N, B> {
+    type Context = NativeBlasConv2dContext<N>;
+
+    fn conv2d_forward(&self, ctx: &mut Self::Context, y: &mut Self::Tensor, x: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let y_shape = &y.shape().as_slice()[0..4];
+        let w_shape = &w.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape[0], y_shape[0]);
+        assert_eq!(w_shape[0], y_shape[1]);
+        assert_eq!(w_shape[1], x_shape[1]);
+        assert_eq!((y_shape[2], y_shape[3]), conv_info.output_size(x_shape[2], x_shape[3]));
+
+        let (batch_size, channels, rows, cols) = (x_shape[0] as usize, x_shape[1] as usize, x_shape[2] as usize, x_shape[3] as usize);
+        let (filters, out_rows, out_cols) = (y_shape[1] as usize, y_shape[2] as usize, y_shape[3] as usize);
+        let (k_rows, k_cols) = (w_shape[2] as usize, w_shape[3] as usize);
+        let ((pad_top, _), (pad_left, _)) = conv_info.paddings(x_shape[2], x_shape[3]);
+        let (s_row, s_col) = conv_info.strides;
+
+        let k_size = k_rows * k_cols;
+        let out_size = out_rows * out_cols;
+        let x_img_size = channels * rows * cols;
+        let y_img_size = filters * out_size;
+
+        let col = ctx.col(channels * k_size * out_size);
+        let x_s = self.read_tensor(x);
+        let w_s = self.read_tensor(w);
+        let y_s = self.write_tensor(y);
+
+        for bi in 0..batch_size {
+            img_to_col(col, &x_s[bi * x_img_size..], channels, k_rows, k_cols, rows, cols,
+                       out_rows, out_cols, s_row as usize, s_col as usize, pad_top as usize, pad_left as usize);
+
+            let y_img = &mut y_s[bi * y_img_size..(bi + 1) * y_img_size];
+
+            // y = w * col
+            gemm(false, false, filters, out_size, channels * k_size, N::ONE,
+                 w_s, channels * k_size,
+                 col, out_size,
+                 N::ZERO,
+                 y_img, out_size);
+        }
+    }
+
+    fn conv2d_backward_input(&self, ctx: &mut Self::Context, dx: &mut Self::Tensor, dy: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let dx_shape = &dx.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let w_shape = &w.shape().as_slice()[0..4];
+
+        assert_eq!(dx_shape[0], dy_shape[0]);
+        assert_eq!(w_shape[0], dy_shape[1]);
+        assert_eq!(w_shape[1], dx_shape[1]);
+        assert_eq!((dy_shape[2], dy_shape[3]), conv_info.output_size(dx_shape[2], dx_shape[3]));
+
+        let (batch_size, channels, rows, cols) = (dx_shape[0] as usize, dx_shape[1] as usize, dx_shape[2] as usize, dx_shape[3] as usize);
+        let (filters, out_rows, out_cols) = (dy_shape[1] as usize, dy_shape[2] as usize, dy_shape[3] as usize);
+        let (k_rows, k_cols) = (w_shape[2] as usize, w_shape[3] as usize);
+        let ((pad_top, _), (pad_left, _)) = conv_info.paddings(dx_shape[2], dx_shape[3]);
+        let (s_row, s_col) = conv_info.strides;
+
+        let k_size = k_rows * k_cols;
+        let out_size = out_rows * out_cols;
+        let dx_img_size = channels * rows * cols;
+        let dy_img_size = filters * out_size;
+
+        self.fill_scalar(dx, N::ZERO);
+
+        let col = ctx.col(channels * k_size * out_size);
+        let dy_s = self.read_tensor(dy);
+        let w_s = self.read_tensor(w);
+        let dx_s = self.write_tensor(dx);
+
+        for bi in 0..batch_size {
+            let dy_img = &dy_s[bi * dy_img_size..(bi + 1) * dy_img_size];
+
+            // col = w^T * dy
+            gemm(true, false, channels * k_size, out_size, filters, N::ONE,
+                 w_s, channels * k_size,
+                 dy_img, out_size,
+                 N::ZERO,
+                 col, out_size);
+
+            col_to_img(&mut dx_s[bi * dx_img_size..], col, channels, k_rows, k_cols, rows, cols,
+                       out_rows, out_cols, s_row as usize, s_col as usize, pad_top as usize, pad_left as usize);
+        }
+    }
+
+    fn conv2d_backward_filter(&self, ctx: &mut Self::Context, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let dw_shape = &dw.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape[0], dy_shape[0]);
+        assert_eq!(dw_shape[0], dy_shape[1]);
+        assert_eq!(dw_shape[1], x_shape[1]);
+        assert_eq!((dy_shape[2], dy_shape[3]), conv_info.output_size(x_shape[2], x_shape[3]));
+
+        let (batch_size, channels, rows, cols) = (x_shape[0] as usize, x_shape[1] as usize, x_shape[2] as usize, x_shape[3] as usize);
+        let (filters, out_rows, out_cols) = (dy_shape[1] as usize, dy_shape[2] as usize, dy_shape[3] as usize);
+        let (k_rows, k_cols) = (dw_shape[2] as usize, dw_shape[3] as usize);
+        let ((pad_top, _), (pad_left, _)) = conv_info.paddings(x_shape[2], x_shape[3]);
+        let (s_row, s_col) = conv_info.strides;
+
+        let k_size = k_rows * k_cols;
+        let out_size = out_rows * out_cols;
+        let x_img_size = channels * rows * cols;
+        let dy_img_size = filters * out_size;
+
+        self.fill_scalar(dw, N::ZERO);
+
+        let col = ctx.col(channels * k_size * out_size);
+        let x_s = self.read_tensor(x);
+        let dy_s = self.read_tensor(dy);
+        let dw_s = self.write_tensor(dw);
+
+        for bi in 0..batch_size {
+            img_to_col(col, &x_s[bi * x_img_size..], channels, k_rows, k_cols, rows, cols,
+                       out_rows, out_cols, s_row as usize, s_col as usize, pad_top as usize, pad_left as usize);
+
+            let dy_img = &dy_s[bi * dy_img_size..(bi + 1) * dy_img_size];
+
+            // dw += dy * col^T
+            gemm(false, true, filters, channels * k_size, out_size, N::ONE,
+                 dy_img, out_size,
+                 col, out_size,
+                 N::ONE,
+                 dw_s, channels * k_size);
+        }
+    }
+}
+
+impl<N: BlasNumber, B: NativeBackend<N> + BackendMaxPool2d<N>> BackendMaxPool2d<N> for NativeBlas<N, B> {
     #[inline]
     fn max_pool2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
         self.inner.max_pool2d(y, x, conv_info)
@@ -364,7 +763,7 @@ impl<B: NativeBackend<f32> + BackendMaxPool2d<f32>> BackendMaxPool2d<f32> for Na
     }
 }
 
-impl<B: NativeBackend<f32> + BackendAvgPool2d<f32>> BackendAvgPool2d<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendAvgPool2d<N>> BackendAvgPool2d<N> for NativeBlas<N, B> {
     #[inline]
     fn avg_pool2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
         self.inner.avg_pool2d(y, x, conv_info)
@@ -376,9 +775,115 @@ impl<B: NativeBackend<f32> + BackendAvgPool2d<f32>> BackendAvgPool2d<f32> for Na
     }
 }
 
-impl<B: NativeBackend<f32> + BackendPaddingCopy2d<f32>> BackendPaddingCopy2d<f32> for NativeBlas<f32, B> {
+impl<N: BlasNumber, B: NativeBackend<N> + BackendPaddingCopy2d<N>> BackendPaddingCopy2d<N> for NativeBlas<N, B> {
     #[inline]
     fn copy_with_padding2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, y_paddings: (u32, u32), x_paddings: (u32, u32)) {
         self.inner.copy_with_padding2d(y, x, y_paddings, x_paddings)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_tensor<N: NativeNumber, S: Into<TensorShape>>(bac: &Native<N>, shape: S) -> NativeTensor<N> {
+        let mut t = NativeTensor::new(shape);
+        bac.fill_random(&mut t, N::from_f32(-1.0), N::ONE);
+        t
+    }
+
+    fn assert_close<N: NativeNumber>(name: &str, a: &NativeTensor<N>, b: &NativeTensor<N>) {
+        assert_eq!(a.shape(), b.shape());
+
+        for (i, (&x, &y)) in a.read().iter().zip(b.read().iter()).enumerate() {
+            assert!((x - y).abs().to_f32() < 1e-4, "{}[{}]: {} != {}", name, i, x, y);
+        }
+    }
+
+    /// Runs both convolution paths on random data and compares the results
+    fn check_conv2d<N: BlasNumber>(x_shape: (u32, u32, u32, u32), filters: u32, conv_info: Conv2dInfo) {
+        let native: Native<N> = Default::default();
+        let blas: NativeBlas<N, Native<N>> = Default::default();
+        let mut ctx = Default::default();
+
+        let (bs, channels, rows, cols) = x_shape;
+        let (out_rows, out_cols) = conv_info.output_size(rows, cols);
+        let (k_rows, k_cols) = conv_info.kernel;
+
+        let x = random_tensor(&native, x_shape);
+        let w = random_tensor(&native, (filters, channels, k_rows, k_cols));
+        let dy = random_tensor(&native, (bs, filters, out_rows, out_cols));
+
+        let mut y = [NativeTensor::new((bs, filters, out_rows, out_cols)), NativeTensor::new((bs, filters, out_rows, out_cols))];
+        let mut dx = [NativeTensor::new(x_shape), NativeTensor::new(x_shape)];
+        let mut dw = [NativeTensor::new(w.shape().clone()), NativeTensor::new(w.shape().clone())];
+
+        native.conv2d_forward(&mut (), &mut y[0], &x, &w, &conv_info);
+        native.conv2d_backward_input(&mut (), &mut dx[0], &dy, &w, &conv_info);
+        native.conv2d_backward_filter(&mut (), &mut dw[0], &x, &dy, &conv_info);
+
+        // twice, to go through an already allocated scratch buffer
+        for _ in 0..2 {
+            blas.conv2d_forward(&mut ctx, &mut y[1], &x, &w, &conv_info);
+            blas.conv2d_backward_input(&mut ctx, &mut dx[1], &dy, &w, &conv_info);
+            blas.conv2d_backward_filter(&mut ctx, &mut dw[1], &x, &dy, &conv_info);
+
+            assert_close("y", &y[0], &y[1]);
+            assert_close("dx", &dx[0], &dx[1]);
+            assert_close("dw", &dw[0], &dw[1]);
+        }
+    }
+
+    #[test]
+    fn test_conv2d() {
+        check_conv2d::<f32>((2, 3, 8, 8), 4, Conv2dInfo { padding: PaddingKind::Valid, strides: (1, 1), kernel: (3, 3) });
+        check_conv2d::<f32>((2, 3, 8, 8), 4, Conv2dInfo { padding: PaddingKind::Same, strides: (1, 1), kernel: (3, 3) });
+        check_conv2d::<f32>((2, 2, 9, 7), 3, Conv2dInfo { padding: PaddingKind::Same, strides: (2, 2), kernel: (5, 5) });
+        check_conv2d::<f32>((1, 2, 6, 7), 3, Conv2dInfo { padding: PaddingKind::Full, strides: (1, 1), kernel: (2, 3) });
+        check_conv2d::<f32>((3, 1, 10, 9), 2, Conv2dInfo { padding: PaddingKind::Valid, strides: (3, 2), kernel: (4, 2) });
+        check_conv2d::<f64>((2, 3, 8, 8), 4, Conv2dInfo { padding: PaddingKind::Same, strides: (2, 1), kernel: (3, 3) });
+    }
+
+    #[test]
+    fn test_matmul_tt() {
+        let native: Native<f32> = Default::default();
+        let blas: NativeBlas<f32, Native<f32>> = Default::default();
+
+        let a = random_tensor(&native, (5, 3));
+        let b = random_tensor(&native, (4, 5));
+        let mut c = [NativeTensor::new((3, 4)), NativeTensor::new((3, 4))];
+
+        native.matmul_tt(&mut c[0], &a, &b);
+        blas.matmul_tt(&mut c[1], &a, &b);
+
+        assert_close("c", &c[0], &c[1]);
+    }
+
+    #[test]
+    fn test_matmul_f64() {
+        let native: Native<f64> = Default::default();
+        let blas: NativeBlas<f64, Native<f64>> = Default::default();
+
+        let a = random_tensor(&native, (3, 5));
+        let at = random_tensor(&native, (5, 3));
+        let b = random_tensor(&native, (5, 4));
+        let bt = random_tensor(&native, (4, 5));
+        let mut c = [NativeTensor::new((3, 4)), NativeTensor::new((3, 4))];
+
+        native.matmul(&mut c[0], &a, &b);
+        blas.matmul(&mut c[1], &a, &b);
+        assert_close("matmul", &c[0], &c[1]);
+
+        native.matmul_nt(&mut c[0], &a, &bt);
+        blas.matmul_nt(&mut c[1], &a, &bt);
+        assert_close("matmul_nt", &c[0], &c[1]);
+
+        native.matmul_tn(&mut c[0], &at, &b);
+        blas.matmul_tn(&mut c[1], &at, &b);
+        assert_close("matmul_tn", &c[0], &c[1]);
+
+        native.matmul_tt(&mut c[0], &at, &bt);
+        blas.matmul_tt(&mut c[1], &at, &bt);
+        assert_close("matmul_tt", &c[0], &c[1]);
+    }
+}
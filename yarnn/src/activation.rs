@@ -0,0 +1,88 @@
+use crate::native::NativeNumber;
+
+/// sqrt(2 / pi) of the tanh approximation of GELU
+const GELU_C: f64 = 0.797_884_560_802_865_4;
+
+/// Cubic coefficient of the tanh approximation of GELU
+const GELU_A: f64 = 0.044_715;
+
+
+/// Element-wise activation function, applied by `BackendActivation`.
+/// The scalar `f` and `df` here are the reference definitions the native backends run on,
+/// so a new activation only needs a variant and its two formulas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Activation {
+    ReLu,
+    /// `x` for positive inputs, `slope * x` otherwise
+    LeakyReLu { slope: f32 },
+    /// `x` for positive inputs, `alpha * (e^x - 1)` otherwise
+    Elu { alpha: f32 },
+    Sigmoid,
+    Tanh,
+    /// Gaussian error linear unit, tanh approximation
+    Gelu,
+    /// `x * sigmoid(x)`, also known as SiLU
+    Swish,
+    /// `ln(1 + e^x)`
+    Softplus,
+}
+
+impl Activation {
+    #[inline]
+    pub fn f<N: NativeNumber>(self, x: N) -> N {
+        let zero = N::ZERO;
+        let one = N::ONE;
+
+        match self {
+            Activation::ReLu => if x > zero { x } else { zero },
+            Activation::LeakyReLu { slope } => if x > zero { x } else { N::from_f32(slope) * x },
+            Activation::Elu { alpha } => if x > zero { x } else { N::from_f32(alpha) * (x.exp() - one) },
+            Activation::Sigmoid => sigmoid(x),
+            Activation::Tanh => x.tanh(),
+            Activation::Gelu => N::from_f32(0.5) * x * (one + gelu_tanh_arg(x).tanh()),
+            Activation::Swish => x * sigmoid(x),
+
+            // max(x, 0) + ln(1 + e^-|x|) doesn't overflow for large x
+            Activation::Softplus => x.max(zero) + (-x.abs()).exp().ln_1p(),
+        }
+    }
+
+    /// Derivative at `x`, where `y = f(x)`
+    #[inline]
+    pub fn df<N: NativeNumber>(self, x: N, y: N) -> N {
+        let zero = N::ZERO;
+        let one = N::ONE;
+        let half = N::from_f32(0.5);
+
+        match self {
+            Activation::ReLu => if x > zero { one } else { zero },
+            Activation::LeakyReLu { slope } => if x > zero { one } else { N::from_f32(slope) },
+            Activation::Elu { alpha } => if x > zero { one } else { y + N::from_f32(alpha) },
+            Activation::Sigmoid => y * (one - y),
+            Activation::Tanh => one - y * y,
+            Activation::Gelu => {
+                let t = gelu_tanh_arg(x).tanh();
+                let dt = N::from_f64(GELU_C) * (one + N::from_f64(3.0 * GELU_A) * x * x);
+
+                half * (one + t) + half * x * (one - t * t) * dt
+            },
+            Activation::Swish => {
+                let s = sigmoid(x);
+
+                s + x * s * (one - s)
+            },
+            Activation::Softplus => sigmoid(x),
+        }
+    }
+}
+
+#[inline]
+fn sigmoid<N: NativeNumber>(x: N) -> N {
+    N::ONE / (N::ONE + (-x).exp())
+}
+
+/// `sqrt(2 / pi) * (x + 0.044715 * x^3)`, the argument of tanh in GELU
+#[inline]
+fn gelu_tanh_arg<N: NativeNumber>(x: N) -> N {
+    N::from_f64(GELU_C) * (x + N::from_f64(GELU_A) * x * x * x)
+}
@@ -1,4 +1,5 @@
 use crate::tensor::{Tensor};
+pub use crate::activation::Activation;
 
 
 pub trait Backend<N> {
@@ -8,6 +9,9 @@ pub trait Backend<N> {
     fn load_tensor_u8(&self, t: &mut Self::Tensor, data: &[u8]);
     fn load_tensor_f32(&self, t: &mut Self::Tensor, data: &[f32]);
     fn scalar_f32(&self, val: f32) -> N;
+    fn scalar_f64(&self, val: f64) -> N;
+    /// Value of a scalar such as a `BackendReduce` result, exact for `f32` and `f64` scalars
+    fn scalar_to_f64(&self, val: N) -> f64;
     fn fill_scalar(&self, t: &mut Self::Tensor, scalar: N);
     fn fill_random(&self, t: &mut Self::Tensor, from: N, to: N);
     fn print_tensor(&self, t: &Self::Tensor);
@@ -36,6 +40,16 @@ impl <'a, N, T: Backend<N>> Backend<N> for &'a T {
         (**self).scalar_f32(val)
     }
 
+    #[inline]
+    fn scalar_f64(&self, val: f64) -> N {
+        (**self).scalar_f64(val)
+    }
+
+    #[inline]
+    fn scalar_to_f64(&self, val: N) -> f64 {
+        (**self).scalar_to_f64(val)
+    }
+
     #[inline]
     fn fill_scalar(&self, t: &mut Self::Tensor, scalar: N) {
         (**self).fill_scalar(t, scalar)
@@ -98,38 +112,41 @@ impl <'a, N, T: BackendBias<N>> BackendBias<N> for &'a T {
     }
 }
 
-pub trait BackendSigmoid<N>: Backend<N> {
-    fn sigmoid(&self, dst: &mut Self::Tensor, data: &Self::Tensor);
-    fn sigmoid_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor);
+/// Per channel biases for `(batch, channels, rows, cols)` tensors.
+pub trait BackendBias2d<N>: Backend<N> {
+    fn bias_add2d(&self, dst: &mut Self::Tensor, bias: &Self::Tensor);
+    fn bias_grad2d(&self, bias: &mut Self::Tensor, inputs: &Self::Tensor);
 }
 
-impl <'a, N, T: BackendSigmoid<N>> BackendSigmoid<N> for &'a T {
+impl <'a, N, T: BackendBias2d<N>> BackendBias2d<N> for &'a T {
     #[inline]
-    fn sigmoid(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
-        (**self).sigmoid(dst, data)
+    fn bias_add2d(&self, dst: &mut Self::Tensor, bias: &Self::Tensor) {
+        (**self).bias_add2d(dst, bias)
     }
 
     #[inline]
-    fn sigmoid_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
-        (**self).sigmoid_grad(dst, z, d)
+    fn bias_grad2d(&self, bias: &mut Self::Tensor, inputs: &Self::Tensor) {
+        (**self).bias_grad2d(bias, inputs)
     }
 }
 
 
-pub trait BackendReLu<N>: Backend<N> {
-    fn relu(&self, dst: &mut Self::Tensor, data: &Self::Tensor);
-    fn relu_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor);
+pub trait BackendActivation<N>: Backend<N> {
+    fn activation(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor);
+
+    /// `dst = act'(x) * dy`, where `y` is the result of `activation` on `x`
+    fn activation_grad(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor);
 }
 
-impl <'a, N, T: BackendReLu<N>> BackendReLu<N> for &'a T {
+impl <'a, N, T: BackendActivation<N>> BackendActivation<N> for &'a T {
     #[inline]
-    fn relu(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
-        (**self).relu(dst, data)
+    fn activation(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor) {
+        (**self).activation(act, dst, x)
     }
 
     #[inline]
-    fn relu_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
-        (**self).relu_grad(dst, z, d)
+    fn activation_grad(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
+        (**self).activation_grad(act, dst, x, y, dy)
     }
 }
 
@@ -249,8 +266,46 @@ impl <'a, N, T: BackendAdam<N>> BackendAdam<N> for &'a T {
     }
 }
 
+/// `dst += lr * sqrt(delta_accum + eps) / sqrt(accum + eps) * grads`, the applied update
+/// (without `lr`) is accumulated into `delta_accum` with decay `rho`
+pub trait BackendAdaDelta<N>: BackendScale<N> + BackendAxpys<N> {
+    fn adadelta_p(&self, dst: &mut Self::Tensor, lr: N, grads: &Self::Tensor, accum: &Self::Tensor, delta_accum: &mut Self::Tensor, rho: N, eps: N);
+}
+
+impl <'a, N, T: BackendAdaDelta<N>> BackendAdaDelta<N> for &'a T {
+    #[inline]
+    fn adadelta_p(&self, dst: &mut Self::Tensor, lr: N, grads: &Self::Tensor, accum: &Self::Tensor, delta_accum: &mut Self::Tensor, rho: N, eps: N) {
+        (**self).adadelta_p(dst, lr, grads, accum, delta_accum, rho, eps)
+    }
+}
+
+/// `dst += lr * (mom_coef * moms + grad_coef * grads) / (sqrt(vels) + eps)`
+pub trait BackendNadam<N>: BackendAdam<N> {
+    fn nadam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, grads: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, grad_coef: N, eps: N);
+}
+
+impl <'a, N, T: BackendNadam<N>> BackendNadam<N> for &'a T {
+    #[inline]
+    fn nadam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, grads: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, grad_coef: N, eps: N) {
+        (**self).nadam_p(dst, lr, moms, grads, vels, mom_coef, grad_coef, eps)
+    }
+}
+
+/// `update = mom_coef * moms / (sqrt(vel_coef * vels) + eps) + weight_decay * params`
+pub trait BackendLamb<N>: BackendAdam<N> + BackendReduce<N> {
+    fn lamb_update(&self, update: &mut Self::Tensor, params: &Self::Tensor, moms: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, vel_coef: N, eps: N, weight_decay: N);
+}
+
+impl <'a, N, T: BackendLamb<N>> BackendLamb<N> for &'a T {
+    #[inline]
+    fn lamb_update(&self, update: &mut Self::Tensor, params: &Self::Tensor, moms: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, vel_coef: N, eps: N, weight_decay: N) {
+        (**self).lamb_update(update, params, moms, vels, mom_coef, vel_coef, eps, weight_decay)
+    }
+}
+
 pub trait BackendSoftmax<N>: BackendCopy<N> {
     fn softmax(&self, y: &mut Self::Tensor, x: &Self::Tensor);
+    fn softmax_grad(&self, dx: &mut Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor);
 }
 
 impl <'a, N, T: BackendSoftmax<N>> BackendSoftmax<N> for &'a T {
@@ -258,6 +313,226 @@ impl <'a, N, T: BackendSoftmax<N>> BackendSoftmax<N> for &'a T {
     fn softmax(&self, y: &mut Self::Tensor, x: &Self::Tensor) {
         (**self).softmax(y, x)
     }
+
+    #[inline]
+    fn softmax_grad(&self, dx: &mut Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
+        (**self).softmax_grad(dx, y, dy)
+    }
+}
+
+pub trait BackendCrossEntropy<N>: Backend<N> {
+    fn cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+    fn cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+    fn softmax_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendCrossEntropy<N>> BackendCrossEntropy<N> for &'a T {
+    #[inline]
+    fn cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).cross_entropy(dst, pred, target)
+    }
+
+    #[inline]
+    fn cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).cross_entropy_grad(dst, pred, target)
+    }
+
+    #[inline]
+    fn softmax_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        (**self).softmax_cross_entropy(dst, logits, target)
+    }
+}
+
+pub trait BackendBinaryCrossEntropy<N>: Backend<N> {
+    fn binary_cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+    fn binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+
+    /// Binary cross entropy of `sigmoid(logits)`, computed without the sigmoid
+    fn sigmoid_binary_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor);
+    fn sigmoid_binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendBinaryCrossEntropy<N>> BackendBinaryCrossEntropy<N> for &'a T {
+    #[inline]
+    fn binary_cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).binary_cross_entropy(dst, pred, target)
+    }
+
+    #[inline]
+    fn binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).binary_cross_entropy_grad(dst, pred, target)
+    }
+
+    #[inline]
+    fn sigmoid_binary_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        (**self).sigmoid_binary_cross_entropy(dst, logits, target)
+    }
+
+    #[inline]
+    fn sigmoid_binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        (**self).sigmoid_binary_cross_entropy_grad(dst, logits, target)
+    }
+}
+
+pub trait BackendHuber<N>: Backend<N> {
+    fn huber(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N);
+    fn huber_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N);
+}
+
+impl <'a, N, T: BackendHuber<N>> BackendHuber<N> for &'a T {
+    #[inline]
+    fn huber(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        (**self).huber(dst, pred, target, delta)
+    }
+
+    #[inline]
+    fn huber_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        (**self).huber_grad(dst, pred, target, delta)
+    }
+}
+
+pub trait BackendHinge<N>: Backend<N> {
+    /// Multi-class hinge `max(0, 1 + pred_j - pred_y)` per class `j`, where `y` is the argmax of the target row
+    fn hinge(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+    fn hinge_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendHinge<N>> BackendHinge<N> for &'a T {
+    #[inline]
+    fn hinge(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).hinge(dst, pred, target)
+    }
+
+    #[inline]
+    fn hinge_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).hinge_grad(dst, pred, target)
+    }
+}
+
+pub trait BackendKlDivergence<N>: Backend<N> {
+    fn kl_divergence(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+    fn kl_divergence_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendKlDivergence<N>> BackendKlDivergence<N> for &'a T {
+    #[inline]
+    fn kl_divergence(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).kl_divergence(dst, pred, target)
+    }
+
+    #[inline]
+    fn kl_divergence_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        (**self).kl_divergence_grad(dst, pred, target)
+    }
+}
+
+pub trait BackendNll<N>: Backend<N> {
+    fn nll(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor);
+    fn nll_grad(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendNll<N>> BackendNll<N> for &'a T {
+    #[inline]
+    fn nll(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        (**self).nll(dst, log_probs, target)
+    }
+
+    #[inline]
+    fn nll_grad(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        (**self).nll_grad(dst, log_probs, target)
+    }
+}
+
+/// Reductions to a scalar of the tensor precision, see `Backend::scalar_to_f64`
+pub trait BackendReduce<N>: Backend<N> {
+    fn sum(&self, t: &Self::Tensor) -> N;
+
+    /// Euclidean norm over all elements
+    fn norm_l2(&self, t: &Self::Tensor) -> N;
+}
+
+impl <'a, N, T: BackendReduce<N>> BackendReduce<N> for &'a T {
+    #[inline]
+    fn sum(&self, t: &Self::Tensor) -> N {
+        (**self).sum(t)
+    }
+
+    #[inline]
+    fn norm_l2(&self, t: &Self::Tensor) -> N {
+        (**self).norm_l2(t)
+    }
+}
+
+pub trait BackendClamp<N>: Backend<N> {
+    fn clamp(&self, t: &mut Self::Tensor, min: N, max: N);
+}
+
+impl <'a, N, T: BackendClamp<N>> BackendClamp<N> for &'a T {
+    #[inline]
+    fn clamp(&self, t: &mut Self::Tensor, min: N, max: N) {
+        (**self).clamp(t, min, max)
+    }
+}
+
+pub trait BackendDropout<N>: BackendCopy<N> {
+    fn dropout(&self, y: &mut Self::Tensor, mask: &mut Self::Tensor, x: &Self::Tensor, rate: N, seed: u64);
+    fn dropout_grad(&self, dx: &mut Self::Tensor, mask: &Self::Tensor, dy: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendDropout<N>> BackendDropout<N> for &'a T {
+    #[inline]
+    fn dropout(&self, y: &mut Self::Tensor, mask: &mut Self::Tensor, x: &Self::Tensor, rate: N, seed: u64) {
+        (**self).dropout(y, mask, x, rate, seed)
+    }
+
+    #[inline]
+    fn dropout_grad(&self, dx: &mut Self::Tensor, mask: &Self::Tensor, dy: &Self::Tensor) {
+        (**self).dropout_grad(dx, mask, dy)
+    }
+}
+
+/// Batch normalization over the second axis: `(batch, features)` tensors are
+/// normalized per feature, `(batch, channels, rows, cols)` tensors per channel.
+pub trait BackendBatchNorm<N>: BackendScale<N> + BackendAxpy<N> {
+    fn batch_norm_stats(&self, mean: &mut Self::Tensor, var: &mut Self::Tensor, x: &Self::Tensor);
+    fn batch_norm(&self, y: &mut Self::Tensor, xhat: &mut Self::Tensor, x: &Self::Tensor,
+                  mean: &Self::Tensor, var: &Self::Tensor, gamma: &Self::Tensor, beta: &Self::Tensor, eps: N);
+    fn batch_norm_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor,
+                       var: &Self::Tensor, gamma: &Self::Tensor, eps: N);
+    /// `batch_norm_grad` for statistics that don't depend on the batch, e.g. the running ones
+    fn batch_norm_inference_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor,
+                                 var: &Self::Tensor, gamma: &Self::Tensor, eps: N);
+    fn batch_norm_param_grads(&self, dgamma: &mut Self::Tensor, dbeta: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor);
+}
+
+impl <'a, N, T: BackendBatchNorm<N>> BackendBatchNorm<N> for &'a T {
+    #[inline]
+    fn batch_norm_stats(&self, mean: &mut Self::Tensor, var: &mut Self::Tensor, x: &Self::Tensor) {
+        (**self).batch_norm_stats(mean, var, x)
+    }
+
+    #[inline]
+    fn batch_norm(&self, y: &mut Self::Tensor, xhat: &mut Self::Tensor, x: &Self::Tensor,
+                  mean: &Self::Tensor, var: &Self::Tensor, gamma: &Self::Tensor, beta: &Self::Tensor, eps: N) {
+        (**self).batch_norm(y, xhat, x, mean, var, gamma, beta, eps)
+    }
+
+    #[inline]
+    fn batch_norm_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor,
+                       var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        (**self).batch_norm_grad(dx, dy, xhat, var, gamma, eps)
+    }
+
+    #[inline]
+    fn batch_norm_inference_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor,
+                                 var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        (**self).batch_norm_inference_grad(dx, dy, var, gamma, eps)
+    }
+
+    #[inline]
+    fn batch_norm_param_grads(&self, dgamma: &mut Self::Tensor, dbeta: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor) {
+        (**self).batch_norm_param_grads(dgamma, dbeta, dy, xhat)
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -274,30 +549,66 @@ pub struct Conv2dInfo {
     pub kernel: (u32, u32),
 }
 
+impl Conv2dInfo {
+    /// Zero padding around a `rows x cols` input as `((top, bottom), (left, right))`.
+    /// `Same` splits the padding like TensorFlow, with the extra row or column at the bottom or right.
+    pub fn paddings(&self, rows: u32, cols: u32) -> ((u32, u32), (u32, u32)) {
+        let (k_rows, k_cols) = self.kernel;
+        let (s_row, s_col) = self.strides;
+
+        match self.padding {
+            PaddingKind::Valid => ((0, 0), (0, 0)),
+            PaddingKind::Full => ((k_rows - 1, k_rows - 1), (k_cols - 1, k_cols - 1)),
+            PaddingKind::Same => {
+                let same = |size: u32, k: u32, s: u32| {
+                    let out = size.div_ceil(s);
+                    let total = ((out - 1) * s + k).saturating_sub(size);
+
+                    (total / 2, total - total / 2)
+                };
+
+                (same(rows, k_rows, s_row), same(cols, k_cols, s_col))
+            }
+        }
+    }
+
+    /// Output size for a `rows x cols` input: `O = (W - K + P) / S + 1`.
+    pub fn output_size(&self, rows: u32, cols: u32) -> (u32, u32) {
+        let ((top, bottom), (left, right)) = self.paddings(rows, cols);
+
+        (
+            (rows + top + bottom - self.kernel.0) / self.strides.0 + 1,
+            (cols + left + right - self.kernel.1) / self.strides.1 + 1,
+        )
+    }
+}
+
+/// Filters are laid out as `(out_channels, in_channels, kernel_rows, kernel_cols)`.
 pub trait BackendConv2d<N>: Backend<N> {
-    type Context;
+    /// Backend state kept by each `Conv2d` layer between calls, e.g. scratch buffers
+    type Context: Default;
 
-    fn conv2d_forward(&self, y: &mut Self::Tensor, x: &Self::Tensor, filter: &Self::Tensor, conv_info: &Conv2dInfo);
-    fn conv2d_backward_input(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, filter: &Self::Tensor, conv_info: &Conv2dInfo);
-    fn conv2d_backward_filter(&self, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo);
+    fn conv2d_forward(&self, ctx: &mut Self::Context, y: &mut Self::Tensor, x: &Self::Tensor, filter: &Self::Tensor, conv_info: &Conv2dInfo);
+    fn conv2d_backward_input(&self, ctx: &mut Self::Context, dx: &mut Self::Tensor, dy: &Self::Tensor, filter: &Self::Tensor, conv_info: &Conv2dInfo);
+    fn conv2d_backward_filter(&self, ctx: &mut Self::Context, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo);
 }
 
 impl <'a, N, T: BackendConv2d<N>> BackendConv2d<N> for &'a T {
-    type Context = ();
+    type Context = T::Context;
     
     #[inline]
-    fn conv2d_forward(&self, y: &mut Self::Tensor, x: &Self::Tensor, filters: &Self::Tensor, conv_info: &Conv2dInfo) {
-        (**self).conv2d_forward(y, x, filters, conv_info)
+    fn conv2d_forward(&self, ctx: &mut Self::Context, y: &mut Self::Tensor, x: &Self::Tensor, filters: &Self::Tensor, conv_info: &Conv2dInfo) {
+        (**self).conv2d_forward(ctx, y, x, filters, conv_info)
     }
     
     #[inline]
-    fn conv2d_backward_input(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, filters: &Self::Tensor, conv_info: &Conv2dInfo) {
-        (**self).conv2d_backward_input(dx, dy, filters, conv_info)
+    fn conv2d_backward_input(&self, ctx: &mut Self::Context, dx: &mut Self::Tensor, dy: &Self::Tensor, filters: &Self::Tensor, conv_info: &Conv2dInfo) {
+        (**self).conv2d_backward_input(ctx, dx, dy, filters, conv_info)
     }
     
     #[inline]
-    fn conv2d_backward_filter(&self, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
-        (**self).conv2d_forward(dw, x, dy, conv_info)
+    fn conv2d_backward_filter(&self, ctx: &mut Self::Context, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
+        (**self).conv2d_backward_filter(ctx, dw, x, dy, conv_info)
     }
 }
 
@@ -0,0 +1,362 @@
+use crate::backend::Backend;
+use crate::tensor::{Tensor, TensorShape};
+
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+
+
+/// Indexed collection of `(input, target)` samples
+pub trait Dataset {
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Shape of a single input, without the batch axis
+    fn input_shape(&self) -> TensorShape;
+
+    /// Shape of a single target, without the batch axis
+    fn target_shape(&self) -> TensorShape;
+
+    /// Writes sample `index` into `input` and `target`, sized after `input_shape` and `target_shape`
+    fn sample(&self, index: usize, input: &mut [f32], target: &mut [f32]);
+}
+
+/// Writes `labels` as one-hot rows of `classes` values into `dst`
+pub fn one_hot<L: Copy + Into<u32>>(labels: &[L], classes: usize, dst: &mut [f32]) {
+    assert_eq!(dst.len(), labels.len() * classes);
+
+    for (row, &label) in dst.chunks_mut(classes).zip(labels.iter()) {
+        let label = label.into() as usize;
+
+        assert!(label < classes, "label {} is out of range", label);
+
+        for val in row.iter_mut() {
+            *val = 0.0;
+        }
+
+        row[label] = 1.0;
+    }
+}
+
+/// Dataset over `f32` inputs and targets, samples stored one after another
+pub struct SliceDataset<'a> {
+    inputs: &'a [f32],
+    targets: &'a [f32],
+    input_shape: TensorShape,
+    target_shape: TensorShape,
+}
+
+impl<'a> SliceDataset<'a> {
+    pub fn new<S: Into<TensorShape>, T: Into<TensorShape>>(inputs: &'a [f32], input_shape: S, targets: &'a [f32], target_shape: T) -> Self {
+        let input_shape = input_shape.into();
+        let target_shape = target_shape.into();
+
+        assert_eq!(inputs.len() / input_shape.size(), targets.len() / target_shape.size());
+
+        Self {
+            inputs,
+            targets,
+            input_shape,
+            target_shape,
+        }
+    }
+}
+
+impl<'a> Dataset for SliceDataset<'a> {
+    fn len(&self) -> usize {
+        self.inputs.len() / self.input_shape.size()
+    }
+
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+
+    fn target_shape(&self) -> TensorShape {
+        self.target_shape.clone()
+    }
+
+    fn sample(&self, index: usize, input: &mut [f32], target: &mut [f32]) {
+        let (input_size, target_size) = (input.len(), target.len());
+
+        input.copy_from_slice(&self.inputs[index * input_size .. (index + 1) * input_size]);
+        target.copy_from_slice(&self.targets[index * target_size .. (index + 1) * target_size]);
+    }
+}
+
+/// Dataset over `u8` samples (e.g. images) with class labels, targets are one-hot encoded
+pub struct LabeledDataset<'a> {
+    samples: &'a [u8],
+    labels: &'a [u8],
+    input_shape: TensorShape,
+    classes: u32,
+    scale: f32,
+}
+
+impl<'a> LabeledDataset<'a> {
+    /// Sample values are multiplied by `scale`, e.g. `1.0 / 255.0` for images
+    pub fn new<S: Into<TensorShape>>(samples: &'a [u8], input_shape: S, labels: &'a [u8], classes: u32, scale: f32) -> Self {
+        let input_shape = input_shape.into();
+
+        assert_eq!(samples.len(), labels.len() * input_shape.size());
+
+        Self {
+            samples,
+            labels,
+            input_shape,
+            classes,
+            scale,
+        }
+    }
+}
+
+impl<'a> Dataset for LabeledDataset<'a> {
+    fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+
+    fn target_shape(&self) -> TensorShape {
+        TensorShape::new1d(self.classes)
+    }
+
+    fn sample(&self, index: usize, input: &mut [f32], target: &mut [f32]) {
+        let size = input.len();
+
+        for (dst, &src) in input.iter_mut().zip(&self.samples[index * size .. (index + 1) * size]) {
+            *dst = src as f32 * self.scale;
+        }
+
+        one_hot(&self.labels[index ..= index], self.classes as usize, target);
+    }
+}
+
+pub struct DataLoaderConfig {
+    pub batch_size: u32,
+    pub shuffle: bool,
+    pub seed: u64,
+    /// Skip the last batch of an epoch if it has less than `batch_size` samples
+    pub drop_last: bool,
+}
+
+impl Default for DataLoaderConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            shuffle: false,
+            seed: 0,
+            drop_last: false,
+        }
+    }
+}
+
+/// Splits a dataset into mini-batches, reshuffling every epoch if asked to.
+/// Shuffling is reproducible: the order of an epoch depends only on the seed and the epoch number.
+pub struct DataLoader<'a, D: Dataset> {
+    dataset: &'a D,
+    cfg: DataLoaderConfig,
+    epoch: u64,
+}
+
+impl<'a, D: Dataset> DataLoader<'a, D> {
+    pub fn new(dataset: &'a D, cfg: DataLoaderConfig) -> Self {
+        assert!(cfg.batch_size > 0, "batch size must be positive");
+
+        Self {
+            dataset,
+            cfg,
+            epoch: 0,
+        }
+    }
+
+    /// Number of batches per epoch
+    pub fn len(&self) -> usize {
+        let batch_size = self.cfg.batch_size as usize;
+
+        if self.cfg.drop_last {
+            self.dataset.len() / batch_size
+        } else {
+            self.dataset.len().div_ceil(batch_size)
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Makes the next `epoch` call return the batches of epoch `epoch` (counting from 0),
+    /// e.g. to continue the shuffling order of an interrupted training
+    #[inline]
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
+    /// Batches of the next epoch
+    pub fn epoch(&mut self) -> Batches<'a, D> {
+        let mut indices: Vec<usize> = (0 .. self.dataset.len()).collect();
+
+        if self.cfg.shuffle {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(self.cfg.seed.wrapping_add(self.epoch));
+
+            indices.shuffle(&mut rng);
+        }
+
+        self.epoch += 1;
+
+        let batch_size = self.cfg.batch_size as usize;
+
+        if self.cfg.drop_last {
+            indices.truncate(indices.len() - indices.len() % batch_size);
+        }
+
+        Batches {
+            dataset: self.dataset,
+            indices,
+            batch_size,
+            pos: 0,
+            input: vec![0.0; self.dataset.input_shape().size() * batch_size],
+            target: vec![0.0; self.dataset.target_shape().size() * batch_size],
+        }
+    }
+}
+
+/// Batches of a single epoch, see `DataLoader::epoch`
+pub struct Batches<'a, D: Dataset> {
+    dataset: &'a D,
+    indices: Vec<usize>,
+    batch_size: usize,
+    pos: usize,
+    input: Vec<f32>,
+    target: Vec<f32>,
+}
+
+impl<'a, D: Dataset> Batches<'a, D> {
+    /// Loads the next batch into `inputs` and `targets`, resizing them to the batch size.
+    /// Returns the number of samples in the batch, or `None` at the end of the epoch.
+    pub fn next_batch<N, B: Backend<N>>(&mut self, backend: &B, inputs: &mut B::Tensor, targets: &mut B::Tensor) -> Option<usize> {
+        if self.pos >= self.indices.len() {
+            return None;
+        }
+
+        let bs = self.batch_size.min(self.indices.len() - self.pos);
+        let input_shape = self.dataset.input_shape();
+        let target_shape = self.dataset.target_shape();
+        let (input_size, target_size) = (input_shape.size(), target_shape.size());
+
+        for (i, &index) in self.indices[self.pos .. self.pos + bs].iter().enumerate() {
+            self.dataset.sample(
+                index,
+                &mut self.input[i * input_size .. (i + 1) * input_size],
+                &mut self.target[i * target_size .. (i + 1) * target_size],
+            );
+        }
+
+        self.pos += bs;
+
+        load_batch(backend, inputs, bs, input_shape, &self.input[0 .. bs * input_size]);
+        load_batch(backend, targets, bs, target_shape, &self.target[0 .. bs * target_size]);
+
+        Some(bs)
+    }
+}
+
+fn load_batch<N, B: Backend<N>>(backend: &B, t: &mut B::Tensor, bs: usize, shape: TensorShape, data: &[f32]) {
+    let mut batch_shape = TensorShape::new1d(bs as u32);
+    batch_shape.append(shape);
+
+    if t.shape() != &batch_shape {
+        t.resize(batch_shape);
+    }
+
+    backend.load_tensor_f32(t, data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{Native, NativeTensor};
+
+    fn epoch_targets(loader: &mut DataLoader<SliceDataset>) -> Vec<Vec<f32>> {
+        let backend: Native<f32> = Default::default();
+        let mut inputs = NativeTensor::new(());
+        let mut targets = NativeTensor::new(());
+        let mut batches = loader.epoch();
+        let mut res = Vec::new();
+
+        while let Some(bs) = batches.next_batch(&backend, &mut inputs, &mut targets) {
+            assert_eq!(inputs.shape(), &TensorShape::new2d(bs as u32, 2));
+
+            res.push(targets.read().to_vec());
+        }
+
+        res
+    }
+
+    #[test]
+    fn test_data_loader() {
+        let inputs: Vec<f32> = (0 .. 14).map(|x| x as f32).collect();
+        let targets: Vec<f32> = (0 .. 7).map(|x| x as f32).collect();
+        let dataset = SliceDataset::new(&inputs, (2, ), &targets, (1, ));
+
+        let mut loader = DataLoader::new(&dataset, DataLoaderConfig { batch_size: 3, ..Default::default() });
+
+        assert_eq!(loader.len(), 3);
+        assert_eq!(epoch_targets(&mut loader), vec![vec![0.0, 1.0, 2.0], vec![3.0, 4.0, 5.0], vec![6.0]]);
+
+        let mut loader = DataLoader::new(&dataset, DataLoaderConfig { batch_size: 3, drop_last: true, ..Default::default() });
+
+        assert_eq!(loader.len(), 2);
+        assert_eq!(epoch_targets(&mut loader).len(), 2);
+    }
+
+    #[test]
+    fn test_data_loader_shuffle() {
+        let inputs: Vec<f32> = (0 .. 64).map(|x| x as f32).collect();
+        let targets: Vec<f32> = (0 .. 32).map(|x| x as f32).collect();
+        let dataset = SliceDataset::new(&inputs, (2, ), &targets, (1, ));
+        let cfg = || DataLoaderConfig { batch_size: 32, shuffle: true, seed: 42, ..Default::default() };
+
+        let mut a = DataLoader::new(&dataset, cfg());
+        let mut b = DataLoader::new(&dataset, cfg());
+
+        let first = epoch_targets(&mut a);
+        let second = epoch_targets(&mut a);
+
+        assert_eq!(first, epoch_targets(&mut b));
+        assert_eq!(second, epoch_targets(&mut b));
+        assert_ne!(first, second);
+
+        let mut c = DataLoader::new(&dataset, cfg());
+        c.set_epoch(1);
+
+        assert_eq!(second, epoch_targets(&mut c));
+
+        let mut sorted = first[0].clone();
+        sorted.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        assert_eq!(sorted, targets);
+    }
+
+    #[test]
+    fn test_one_hot() {
+        let mut dst = [0.5; 6];
+
+        one_hot(&[2u8, 0], 3, &mut dst);
+
+        assert_eq!(dst, [0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+
+        let samples = [0u8, 255, 3, 8];
+        let dataset = LabeledDataset::new(&samples, (2, ), &[1, 0], 2, 0.5);
+        let (mut input, mut target) = ([0.0; 2], [0.0; 2]);
+
+        dataset.sample(1, &mut input, &mut target);
+
+        assert_eq!(input, [1.5, 4.0]);
+        assert_eq!(target, [1.0, 0.0]);
+    }
+}
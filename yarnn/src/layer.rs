@@ -1,5 +1,6 @@
 use crate::backend::Backend;
 use crate::optimizer::Optimizer;
+use crate::params::ParamsVisitor;
 use crate::tensor::{Tensor, TensorShape};
 
 // use core::marker::PhantomData;
@@ -35,6 +36,10 @@ pub trait Layer<N, B, O>
     #[inline]
     fn optimize(&mut self, _backend: &B, _optimizer: &O) {}
 
+    /// Passes every parameter and state tensor of the layer to `visitor`, in a fixed order
+    #[inline]
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, _visitor: &mut V) {}
+
     fn fmt(&self, f: &mut core::fmt::Formatter, padding: usize) -> core::fmt::Result {
         writeln!(f, "{}{} -> {}[{}] -> {}", "".repeat(padding), self.input_shape(), self.name(), self.param_count(), self.output_shape())?;
 
@@ -66,6 +71,12 @@ pub trait LayerExt<N, B, O>: Layer<N, B, O>
 pub trait LayerContext<N, B: Backend<N>>: Default {
     fn outputs(&self) -> &B::Tensor;
     fn deltas(&self) -> &B::Tensor;
+
+    /// Switches between training and inference behaviour for layers
+    /// which differ between the two (e.g. `Dropout`). Contexts are in
+    /// training mode by default.
+    #[inline]
+    fn set_training(&mut self, _training: bool) {}
 }
 
 
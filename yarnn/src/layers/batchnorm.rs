@@ -0,0 +1,259 @@
+use crate::tensor::{Tensor, TensorShape};
+use crate::layer::{Layer, LayerExt, LayerContext, DefaultLayerContext};
+use crate::params::{Params, ParamsVisitor};
+use crate::backend::{Backend, BackendBatchNorm};
+use crate::optimizer::Optimizer;
+use core::cell::RefCell;
+
+pub struct BatchNormConfig {
+    pub momentum: f32,
+    pub epsilon: f32,
+}
+
+impl Default for BatchNormConfig {
+    fn default() -> Self {
+        Self {
+            momentum: 0.99,
+            epsilon: 0.001,
+        }
+    }
+}
+
+pub struct BatchNormContext<N, B>
+    where B: Backend<N>,
+{
+    base: DefaultLayerContext<N, B>,
+    xhat: B::Tensor,
+    mean: B::Tensor,
+    var: B::Tensor,
+    training: bool,
+}
+
+impl <N, B> Default for BatchNormContext<N, B>
+    where B: Backend<N>,
+{
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            xhat: B::Tensor::new(()),
+            mean: B::Tensor::new(()),
+            var: B::Tensor::new(()),
+            training: true,
+        }
+    }
+}
+
+impl <N, B> LayerContext<N, B> for BatchNormContext<N, B>
+    where B: Backend<N>,
+{
+    #[inline]
+    fn outputs(&self) -> &B::Tensor {
+        &self.base.outputs
+    }
+
+    #[inline]
+    fn deltas(&self) -> &B::Tensor {
+        &self.base.deltas
+    }
+
+    #[inline]
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+/// Normalizes `Linear` outputs per unit and `Conv2d` outputs per channel.
+/// The running statistics used in inference advance on every training `forward`.
+pub struct BatchNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    input_shape: TensorShape,
+    momentum: f32,
+    epsilon: f32,
+    gamma: Params<N, B, O>,
+    beta: Params<N, B, O>,
+    running_mean: RefCell<B::Tensor>,
+    running_var: RefCell<B::Tensor>,
+}
+
+impl <N, B, O> Layer<N, B, O> for BatchNorm<N, B, O>
+    where B: Backend<N> + BackendBatchNorm<N>,
+          O: Optimizer<N, B>
+{
+    type Context = BatchNormContext<N, B>;
+
+    fn name(&self) -> &str {
+        "BatchNorm"
+    }
+
+    #[inline]
+    fn param_count(&self) -> usize {
+        self.gamma.params.shape().size() + self.beta.params.shape().size()
+    }
+
+    fn init(&mut self, backend: &B) {
+        backend.fill_scalar(&mut self.gamma.params, backend.scalar_f32(1.0));
+        self.beta.init_zero(backend);
+
+        backend.fill_scalar(self.running_mean.get_mut(), backend.scalar_f32(0.0));
+        backend.fill_scalar(self.running_var.get_mut(), backend.scalar_f32(1.0));
+    }
+
+    #[inline]
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+
+    #[inline]
+    fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.base.update_outputs_shape(x.shape().get(0), &self.input_shape);
+
+        if ctx.xhat.shape() != x.shape() {
+            ctx.xhat.resize(x.shape().clone());
+        }
+
+        let eps = backend.scalar_f32(self.epsilon);
+
+        if ctx.training {
+            let features = self.gamma.params.shape();
+
+            if ctx.mean.shape() != features {
+                ctx.mean.resize(features.clone());
+                ctx.var.resize(features.clone());
+            }
+
+            backend.batch_norm_stats(&mut ctx.mean, &mut ctx.var, x);
+            backend.batch_norm(&mut ctx.base.outputs, &mut ctx.xhat, x, &ctx.mean, &ctx.var,
+                               &self.gamma.params, &self.beta.params, eps);
+
+            let mut running_mean = self.running_mean.borrow_mut();
+            let mut running_var = self.running_var.borrow_mut();
+
+            // running = momentum * running + (1 - momentum) * batch
+            backend.scale(&mut running_mean, backend.scalar_f32(self.momentum));
+            backend.axpy(&mut running_mean, backend.scalar_f32(1.0 - self.momentum), &ctx.mean);
+
+            backend.scale(&mut running_var, backend.scalar_f32(self.momentum));
+            backend.axpy(&mut running_var, backend.scalar_f32(1.0 - self.momentum), &ctx.var);
+        } else {
+            backend.batch_norm(&mut ctx.base.outputs, &mut ctx.xhat, x, &self.running_mean.borrow(), &self.running_var.borrow(),
+                               &self.gamma.params, &self.beta.params, eps);
+        }
+    }
+
+    #[inline]
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, _: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.base.update_deltas_shape(dy.shape().get(0), &self.input_shape);
+
+        let eps = backend.scalar_f32(self.epsilon);
+
+        if ctx.training {
+            backend.batch_norm_grad(&mut ctx.base.deltas, dy, &ctx.xhat, &ctx.var, &self.gamma.params, eps);
+        } else {
+            backend.batch_norm_inference_grad(&mut ctx.base.deltas, dy, self.running_var.get_mut(), &self.gamma.params, eps);
+        }
+    }
+
+    fn calc_gradients(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
+        let prescaler = 1.0 / x.shape().get(0) as f32;
+
+        backend.batch_norm_param_grads(&mut self.gamma.grads, &mut self.beta.grads, dy, &ctx.xhat);
+        backend.scale(&mut self.gamma.grads, backend.scalar_f32(prescaler));
+        backend.scale(&mut self.beta.grads, backend.scalar_f32(prescaler));
+    }
+
+    #[inline]
+    fn optimize(&mut self, backend: &B, optimizer: &O) {
+        optimizer.update_params(backend, &mut self.gamma.ctx, &mut self.gamma.params, &mut self.gamma.grads);
+        optimizer.update_params(backend, &mut self.beta.ctx, &mut self.beta.params, &mut self.beta.grads);
+    }
+
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+        visitor.visit_params("BatchNorm", "gamma", &mut self.gamma);
+        visitor.visit_params("BatchNorm", "beta", &mut self.beta);
+        visitor.visit_tensor("BatchNorm", "running_mean", self.running_mean.get_mut());
+        visitor.visit_tensor("BatchNorm", "running_var", self.running_var.get_mut());
+    }
+}
+
+impl <N, B, O> LayerExt<N, B, O> for BatchNorm<N, B, O>
+    where B: Backend<N> + BackendBatchNorm<N>,
+          O: Optimizer<N, B>
+{
+    type Config = BatchNormConfig;
+
+    fn create(input_shape: TensorShape, cfg: Self::Config) -> Self {
+        // (units) after `Linear` or (channels, rows, cols) after `Conv2d`
+        assert!(input_shape.dims == 1 || input_shape.dims == 3);
+
+        let features = input_shape.get(0);
+
+        BatchNorm {
+            input_shape,
+            momentum: cfg.momentum,
+            epsilon: cfg.epsilon,
+            gamma: Params::new((features, )),
+            beta: Params::new((features, )),
+            running_mean: RefCell::new(B::Tensor::new((features, ))),
+            running_var: RefCell::new(B::Tensor::new((features, ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{Native, NativeTensor};
+    use crate::optimizers::Sgd;
+
+    type B = Native<f32>;
+
+    fn tensor(backend: &B, shape: (u32, u32), data: &[f32]) -> NativeTensor<f32> {
+        let mut t = NativeTensor::new(shape);
+        backend.load_tensor_f32(&mut t, data);
+        t
+    }
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        assert_eq!(a.len(), b.len());
+
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-6, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_layer() {
+        let backend = B::default();
+        let mut layer = BatchNorm::<f32, B, Sgd<f32, B>>::create((2, ).into(), BatchNormConfig { momentum: 0.9, epsilon: 0.25 });
+
+        layer.init(&backend);
+        backend.load_tensor_f32(&mut layer.gamma.params, &[2.0, -1.0]);
+        backend.load_tensor_f32(&mut layer.beta.params, &[0.5, 1.0]);
+        backend.load_tensor_f32(layer.running_mean.get_mut(), &[1.0, -2.0]);
+        // standard deviations 2 and 1 with epsilon
+        backend.load_tensor_f32(layer.running_var.get_mut(), &[3.75, 0.75]);
+
+        let x = tensor(&backend, (2, 2), &[3.0, 0.0, -1.0, -2.0]);
+        let dy = tensor(&backend, (2, 2), &[1.0, 2.0, -3.0, 0.5]);
+
+        // inference: y = gamma * (x - running_mean) / std + beta, dx = gamma / std * dy
+        let mut ctx = BatchNormContext::default();
+        ctx.set_training(false);
+
+        layer.forward(&backend, &x, &mut ctx);
+        layer.backward(&backend, &dy, &x, &mut ctx);
+
+        assert_close(ctx.outputs().read(), &[2.5, -1.0, -1.5, 1.0]);
+        assert_close(ctx.deltas().read(), &[1.0, -2.0, -3.0, -0.5]);
+
+        // a training forward alone advances the running statistics, batch mean [1, -1] and variance [4, 1]
+        let mut ctx = BatchNormContext::default();
+
+        layer.forward(&backend, &x, &mut ctx);
+
+        assert_close(layer.running_mean.get_mut().read(), &[1.0, -1.9]);
+        assert_close(layer.running_var.get_mut().read(), &[3.775, 0.775]);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::backend::Backend;
 use crate::layer::{Layer, LayerContext};
 use crate::optimizer::Optimizer;
+use crate::params::ParamsVisitor;
 use crate::tensor::TensorShape;
 
 use core::marker::PhantomData;
@@ -43,6 +44,12 @@ impl<N, B, L, R> LayerContext<N, B> for ChainContext<N, B, L, R>
     fn deltas(&self) -> &B::Tensor {
         self.left.deltas()
     }
+
+    #[inline]
+    fn set_training(&mut self, training: bool) {
+        self.left.set_training(training);
+        self.right.set_training(training);
+    }
 } 
 
 pub struct Chain<N, B, O, L, R> 
@@ -143,6 +150,12 @@ impl<N, B, O, L, R> Layer<N, B, O> for Chain<N, B, O, L, R>
         self.right.optimize(backend, optimizer);
     }
 
+    #[inline]
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+        self.left.visit_params(visitor);
+        self.right.visit_params(visitor);
+    }
+
     fn fmt(&self, f: &mut core::fmt::Formatter, padding: usize) -> core::fmt::Result {
         self.left.fmt(f, padding)?;
         self.right.fmt(f, padding)?;
@@ -1,7 +1,7 @@
 use crate::tensor::{Tensor, TensorShape};
-use crate::layer::{Layer, LayerExt, DefaultLayerContext};
-use crate::params::Params;
-use crate::backend::{Backend, Conv2dInfo, PaddingKind, BackendBias, BackendConv2d, BackendScale};
+use crate::layer::{Layer, LayerExt, LayerContext, DefaultLayerContext};
+use crate::params::{Params, ParamsVisitor};
+use crate::backend::{Backend, Conv2dInfo, PaddingKind, BackendBias2d, BackendConv2d, BackendScale};
 use crate::optimizer::Optimizer;
 
 pub struct Conv2dConfig {
@@ -24,6 +24,38 @@ impl Default for Conv2dConfig {
     }
 }
 
+pub struct Conv2dContext<N, B>
+    where B: Backend<N> + BackendConv2d<N>,
+{
+    base: DefaultLayerContext<N, B>,
+    conv: <B as BackendConv2d<N>>::Context,
+}
+
+impl <N, B> Default for Conv2dContext<N, B>
+    where B: Backend<N> + BackendConv2d<N>,
+{
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            conv: Default::default(),
+        }
+    }
+}
+
+impl <N, B> LayerContext<N, B> for Conv2dContext<N, B>
+    where B: Backend<N> + BackendConv2d<N>,
+{
+    #[inline]
+    fn outputs(&self) -> &B::Tensor {
+        &self.base.outputs
+    }
+
+    #[inline]
+    fn deltas(&self) -> &B::Tensor {
+        &self.base.deltas
+    }
+}
+
 pub struct Conv2d<N, B, O> 
     where B: Backend<N>,
           O: Optimizer<N, B>
@@ -37,10 +69,10 @@ pub struct Conv2d<N, B, O>
 }
 
 impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O> 
-    where B: Backend<N> + BackendConv2d<N> + BackendBias<N> + BackendScale<N>,
+    where B: Backend<N> + BackendConv2d<N> + BackendBias2d<N> + BackendScale<N>,
           O: Optimizer<N, B>
 {
-    type Context = DefaultLayerContext<N, B>;
+    type Context = Conv2dContext<N, B>;
 
     fn name(&self) -> &str {
         "Conv2d"
@@ -56,7 +88,10 @@ impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O>
     } 
     
     fn init(&mut self, backend: &B) {
-        self.filters.init_random(backend, self.conv_info.kernel.0 * self.conv_info.kernel.1 + self.filters.params.shape().get(0));
+        let w_shape = self.filters.params.shape();
+        let fan_in = w_shape.get(1) * w_shape.get(2) * w_shape.get(3);
+
+        self.filters.init_random(backend, fan_in + w_shape.get(0));
 
         if self.use_biases {
             self.biases.init_zero(backend);
@@ -71,11 +106,7 @@ impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O>
     #[inline]
     fn output_shape(&self) -> TensorShape {
         let is = self.input_shape.as_slice();
-
-        // O = (W - K + 2P) / S + 1
-
-        let rows = (is[1] - self.conv_info.kernel.0) / self.conv_info.strides.0 + 1;
-        let cols = (is[2] - self.conv_info.kernel.1) / self.conv_info.strides.1 + 1;
+        let (rows, cols) = self.conv_info.output_size(is[1], is[2]);
 
         TensorShape::new3d(
             self.units,
@@ -88,13 +119,12 @@ impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O>
     fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
         assert_eq!(x.shape().dims, 4);
 
-        ctx.update_outputs_shape(x.shape().get(0), &self.output_shape());
+        ctx.base.update_outputs_shape(x.shape().get(0), &self.output_shape());
 
-        backend.conv2d_forward(&mut ctx.outputs, x, &self.filters.params, &self.conv_info);
+        backend.conv2d_forward(&mut ctx.conv, &mut ctx.base.outputs, x, &self.filters.params, &self.conv_info);
 
         if self.use_biases {
-            unimplemented!();
-            // backend.bias_add(y, &self.biases.params);
+            backend.bias_add2d(&mut ctx.base.outputs, &self.biases.params);
         }
     }
 
@@ -102,20 +132,25 @@ impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O>
     fn backward(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
         assert_eq!(dy.shape().dims, 4);
         
-        ctx.update_deltas_shape(x.shape().get(0), &self.input_shape);
+        ctx.base.update_deltas_shape(x.shape().get(0), &self.input_shape);
 
-        backend.conv2d_backward_input(&mut ctx.deltas, dy, &self.filters.params, &self.conv_info);
+        backend.conv2d_backward_input(&mut ctx.conv, &mut ctx.base.deltas, dy, &self.filters.params, &self.conv_info);
     }
 
     #[inline]
-    fn calc_gradients(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, _ctx: &mut Self::Context) {
+    fn calc_gradients(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
         assert_eq!(dy.shape().dims, 4);
         assert_eq!(x.shape().dims, 4);
 
-        backend.conv2d_backward_filter(&mut self.filters.grads, x, dy, &self.conv_info);
+        backend.conv2d_backward_filter(&mut ctx.conv, &mut self.filters.grads, x, dy, &self.conv_info);
         let prescaler = 1.0 / x.shape().get(0) as f32;
 
         backend.scale(&mut self.filters.grads, backend.scalar_f32(prescaler));
+
+        if self.use_biases {
+            backend.bias_grad2d(&mut self.biases.grads, dy);
+            backend.scale(&mut self.biases.grads, backend.scalar_f32(prescaler));
+        }
     }
 
     #[inline]
@@ -123,14 +158,21 @@ impl <N, B, O> Layer<N, B, O> for Conv2d<N, B, O>
         optimizer.update_params(backend, &mut self.filters.ctx, &mut self.filters.params, &mut self.filters.grads);
 
         if self.use_biases {
-            unimplemented!()
-        //     optimizer.update_params(backend, &mut self.biases.ctx, &mut self.biases.params, &self.biases.grads);
+            optimizer.update_params(backend, &mut self.biases.ctx, &mut self.biases.params, &mut self.biases.grads);
+        }
+    }
+
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+        visitor.visit_params("Conv2d", "filters", &mut self.filters);
+
+        if self.use_biases {
+            visitor.visit_params("Conv2d", "biases", &mut self.biases);
         }
     }
 }
 
 impl <N, B, O> LayerExt<N, B, O> for Conv2d<N, B, O> 
-    where B: Backend<N> + BackendConv2d<N> + BackendBias<N> + BackendScale<N>,
+    where B: Backend<N> + BackendConv2d<N> + BackendBias2d<N> + BackendScale<N>,
           O: Optimizer<N, B>
 {
     type Config = Conv2dConfig;
@@ -138,6 +180,8 @@ impl <N, B, O> LayerExt<N, B, O> for Conv2d<N, B, O>
     fn create(input_shape: TensorShape, cfg: Self::Config) -> Self {
         assert!(input_shape.dims == 3);
 
+        let in_channels = input_shape.get(0);
+
         Conv2d {
             input_shape,
             units: cfg.filters,
@@ -147,7 +191,7 @@ impl <N, B, O> LayerExt<N, B, O> for Conv2d<N, B, O>
                 strides: cfg.strides,
             },
             use_biases: cfg.biases,
-            filters: Params::new((cfg.filters, cfg.kernel.0, cfg.kernel.1)),
+            filters: Params::new((cfg.filters, in_channels, cfg.kernel.0, cfg.kernel.1)),
             biases: Params::new((cfg.filters, )),
         }
     }
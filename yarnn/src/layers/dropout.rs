@@ -0,0 +1,191 @@
+use crate::tensor::{Tensor, TensorShape};
+use crate::backend::{Backend, BackendDropout};
+use crate::layer::{Layer, LayerExt, LayerContext, DefaultLayerContext};
+use crate::optimizer::Optimizer;
+use crate::params::ParamsVisitor;
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Seeds of the layers created without one, see `DropoutConfig::seed`
+static NEXT_SEED: AtomicU64 = AtomicU64::new(0);
+
+pub struct DropoutConfig {
+    pub rate: f32,
+    /// Seed of the masks. Layers without one are seeded in the order they are created,
+    /// so the dropout layers of a model draw different masks, and the same ones every run.
+    pub seed: Option<u64>,
+}
+
+impl Default for DropoutConfig {
+    fn default() -> Self {
+        Self {
+            rate: 0.5,
+            seed: None,
+        }
+    }
+}
+
+pub struct DropoutContext<N, B>
+    where B: Backend<N>,
+{
+    base: DefaultLayerContext<N, B>,
+    mask: B::Tensor,
+    training: bool,
+}
+
+impl <N, B> Default for DropoutContext<N, B>
+    where B: Backend<N>,
+{
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            mask: B::Tensor::new(()),
+            training: true,
+        }
+    }
+}
+
+impl <N, B> LayerContext<N, B> for DropoutContext<N, B>
+    where B: Backend<N>,
+{
+    #[inline]
+    fn outputs(&self) -> &B::Tensor {
+        &self.base.outputs
+    }
+
+    #[inline]
+    fn deltas(&self) -> &B::Tensor {
+        &self.base.deltas
+    }
+
+    #[inline]
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+pub struct Dropout<N, B>
+    where B: Backend<N>,
+{
+    input_shape: TensorShape,
+    rate: f32,
+    seed: u64,
+    step: Cell<u64>,
+    _x: PhantomData<fn(N, B)>,
+}
+
+impl <N, B, O> Layer<N, B, O> for Dropout<N, B>
+    where B: Backend<N> + BackendDropout<N>,
+          O: Optimizer<N, B>
+{
+    type Context = DropoutContext<N, B>;
+
+    fn name(&self) -> &str {
+        "Dropout"
+    }
+
+    #[inline]
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+
+    #[inline]
+    fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.base.update_outputs_shape(x.shape().get(0), &self.input_shape);
+
+        if !ctx.training {
+            backend.copy(&mut ctx.base.outputs, x);
+            return;
+        }
+
+        if ctx.mask.shape() != x.shape() {
+            ctx.mask.resize(x.shape().clone());
+        }
+
+        // every training pass draws a new mask, reproducible from the layer seed
+        let step = self.step.get();
+        self.step.set(step + 1);
+
+        // spreads the layer seeds apart, so the step doesn't run one layer into the masks of another
+        let seed = self.seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(step);
+
+        backend.dropout(&mut ctx.base.outputs, &mut ctx.mask, x, backend.scalar_f32(self.rate), seed);
+    }
+
+    #[inline]
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, _: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.base.update_deltas_shape(dy.shape().get(0), &self.input_shape);
+
+        if ctx.training {
+            backend.dropout_grad(&mut ctx.base.deltas, &ctx.mask, dy);
+        } else {
+            backend.copy(&mut ctx.base.deltas, dy);
+        }
+    }
+
+    #[inline]
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+        let mut step = self.step.get() as f32;
+        visitor.visit_scalar("Dropout", "step", &mut step);
+        self.step.set(step as u64);
+    }
+}
+
+impl <N, B, O> LayerExt<N, B, O> for Dropout<N, B>
+    where B: Backend<N> + BackendDropout<N>,
+          O: Optimizer<N, B>
+{
+    type Config = DropoutConfig;
+
+    fn create(input_shape: TensorShape, cfg: Self::Config) -> Self {
+        assert!((0.0 .. 1.0).contains(&cfg.rate));
+
+        Dropout {
+            input_shape,
+            rate: cfg.rate,
+            seed: cfg.seed.unwrap_or_else(|| NEXT_SEED.fetch_add(1, Ordering::Relaxed)),
+            step: Cell::new(0),
+            _x: Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{Native, NativeTensor};
+    use crate::optimizers::Sgd;
+
+    type B = Native<f32>;
+    type O = Sgd<f32, B>;
+
+    fn outputs(backend: &B, layer: &Dropout<f32, B>, x: &NativeTensor<f32>) -> Vec<f32> {
+        let mut ctx = DropoutContext::default();
+
+        Layer::<f32, B, O>::forward(layer, backend, x, &mut ctx);
+
+        ctx.outputs().read().to_vec()
+    }
+
+    #[test]
+    fn test_dropout_seeds() {
+        let backend = B::default();
+        let mut x = NativeTensor::new((4, 64));
+        backend.fill_scalar(&mut x, 1.0);
+
+        let create = |seed| <Dropout<f32, B> as LayerExt<f32, B, O>>::create((64, ).into(), DropoutConfig { rate: 0.5, seed });
+
+        // layers of equal shapes don't share masks by default
+        let (a, b) = (create(None), create(None));
+        assert_ne!(outputs(&backend, &a, &x), outputs(&backend, &b, &x));
+
+        // neither do neighbouring seeds one step apart
+        let (a, b) = (create(Some(1)), create(Some(2)));
+        outputs(&backend, &a, &x);
+        assert_ne!(outputs(&backend, &a, &x), outputs(&backend, &b, &x));
+
+        // an explicit seed repeats the masks
+        assert_eq!(outputs(&backend, &create(Some(3)), &x), outputs(&backend, &create(Some(3)), &x));
+    }
+}
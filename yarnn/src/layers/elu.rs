@@ -0,0 +1,72 @@
+use crate::tensor::{Tensor, TensorShape};
+use crate::backend::{Backend, BackendActivation, Activation};
+use crate::layer::{Layer, LayerExt, DefaultLayerContext};
+use crate::optimizer::Optimizer;
+use core::marker::PhantomData;
+
+pub struct EluConfig {
+    /// Value the negative part saturates to is `-alpha`
+    pub alpha: f32,
+}
+
+impl Default for EluConfig {
+    fn default() -> Self {
+        Self {
+            alpha: 1.0,
+        }
+    }
+}
+
+/// Exponential linear unit: `x` for positive inputs, `alpha * (e^x - 1)` otherwise
+pub struct Elu<N, B> 
+    where B: Backend<N>,
+{
+    input_shape: TensorShape,
+    alpha: f32,
+    _x: PhantomData<fn(N, B)>,
+}
+
+impl <N, B, O> Layer<N, B, O> for Elu<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Context = DefaultLayerContext<N, B>;
+
+    fn name(&self) -> &str {
+        "ELU"
+    }
+    
+    #[inline]
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+    
+    #[inline]
+    fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_outputs_shape(x.shape().get(0), &self.input_shape);
+        
+        backend.activation(Activation::Elu { alpha: self.alpha }, &mut ctx.outputs, x);
+    }
+
+    #[inline]
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_deltas_shape(dy.shape().get(0), &self.input_shape);
+        
+        backend.activation_grad(Activation::Elu { alpha: self.alpha }, &mut ctx.deltas, x, &ctx.outputs, dy);
+    }
+}
+
+impl <N, B, O> LayerExt<N, B, O> for Elu<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Config = EluConfig;
+
+    fn create(input_shape: TensorShape, cfg: Self::Config) -> Self {
+        Elu {
+            input_shape,
+            alpha: cfg.alpha,
+            _x: Default::default()
+        }
+    }
+}
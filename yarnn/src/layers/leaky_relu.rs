@@ -0,0 +1,72 @@
+use crate::tensor::{Tensor, TensorShape};
+use crate::backend::{Backend, BackendActivation, Activation};
+use crate::layer::{Layer, LayerExt, DefaultLayerContext};
+use crate::optimizer::Optimizer;
+use core::marker::PhantomData;
+
+pub struct LeakyReLuConfig {
+    /// Slope of the negative part, has to be positive
+    pub slope: f32,
+}
+
+impl Default for LeakyReLuConfig {
+    fn default() -> Self {
+        Self {
+            slope: 0.01,
+        }
+    }
+}
+
+/// `x` for positive inputs, `slope * x` otherwise
+pub struct LeakyReLu<N, B> 
+    where B: Backend<N>,
+{
+    input_shape: TensorShape,
+    slope: f32,
+    _x: PhantomData<fn(N, B)>,
+}
+
+impl <N, B, O> Layer<N, B, O> for LeakyReLu<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Context = DefaultLayerContext<N, B>;
+
+    fn name(&self) -> &str {
+        "LeakyReLU"
+    }
+    
+    #[inline]
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+    
+    #[inline]
+    fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_outputs_shape(x.shape().get(0), &self.input_shape);
+        
+        backend.activation(Activation::LeakyReLu { slope: self.slope }, &mut ctx.outputs, x);
+    }
+
+    #[inline]
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_deltas_shape(dy.shape().get(0), &self.input_shape);
+        
+        backend.activation_grad(Activation::LeakyReLu { slope: self.slope }, &mut ctx.deltas, x, &ctx.outputs, dy);
+    }
+}
+
+impl <N, B, O> LayerExt<N, B, O> for LeakyReLu<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Config = LeakyReLuConfig;
+
+    fn create(input_shape: TensorShape, cfg: Self::Config) -> Self {
+        LeakyReLu {
+            input_shape,
+            slope: cfg.slope,
+            _x: Default::default()
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::tensor::{Tensor, TensorShape};
 use crate::layer::{Layer, LayerExt, DefaultLayerContext};
-use crate::params::Params;
+use crate::params::{Params, ParamsVisitor};
 use crate::backend::{Backend, BackendGemm, BackendBias, BackendScale};
 use crate::optimizer::Optimizer;
 
@@ -90,8 +90,8 @@ impl <N, B, O> Layer<N, B, O> for Linear<N, B, O>
         backend.scale(&mut self.weights.grads, backend.scalar_f32(prescaler));
 
         if self.use_biases {
-            backend.scale(&mut self.biases.grads, backend.scalar_f32(prescaler));
             backend.bias_grad(&mut self.biases.grads, &dy);
+            backend.scale(&mut self.biases.grads, backend.scalar_f32(prescaler));
         }
     }
 
@@ -103,6 +103,14 @@ impl <N, B, O> Layer<N, B, O> for Linear<N, B, O>
             optimizer.update_params(backend, &mut self.biases.ctx, &mut self.biases.params, &mut self.biases.grads);
         }
     }
+
+    fn visit_params<V: ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+        visitor.visit_params("Linear", "weights", &mut self.weights);
+
+        if self.use_biases {
+            visitor.visit_params("Linear", "biases", &mut self.biases);
+        }
+    }
 }
 
 impl <N, B, O> LayerExt<N, B, O> for Linear<N, B, O> 
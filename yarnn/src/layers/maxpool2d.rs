@@ -7,6 +7,7 @@ use core::marker::PhantomData;
 pub struct MaxPool2dConfig {
     pub pool: (u32, u32),
     pub strides: Option<(u32, u32)>,
+    pub padding: PaddingKind,
 }
 
 impl Default for MaxPool2dConfig {
@@ -14,6 +15,7 @@ impl Default for MaxPool2dConfig {
         Self {
             pool: (2, 2),
             strides: None,
+            padding: PaddingKind::Valid,
         }
     }
 }
@@ -44,11 +46,7 @@ impl <N, B, O> Layer<N, B, O> for MaxPool2d<N, B>
     #[inline]
     fn output_shape(&self) -> TensorShape {
         let is = self.input_shape.as_slice();
-        
-        // O = (W - K + 2P) / S + 1
-
-        let rows = (is[1] - self.conv_info.kernel.0) / self.conv_info.strides.0 + 1;
-        let cols = (is[2] - self.conv_info.kernel.1) / self.conv_info.strides.1 + 1;
+        let (rows, cols) = self.conv_info.output_size(is[1], is[2]);
 
         TensorShape::new3d(
             is[0],
@@ -86,7 +84,7 @@ impl <N, B, O> LayerExt<N, B, O> for MaxPool2d<N, B>
             conv_info: Conv2dInfo {
                 kernel: config.pool,
                 strides: config.strides.unwrap_or(config.pool),
-                padding: PaddingKind::Valid,
+                padding: config.padding,
             },
             _m: Default::default(),
         }
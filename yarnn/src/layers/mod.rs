@@ -2,20 +2,36 @@ mod linear;
 mod sigmoid;
 mod chain;
 mod relu;
+mod tanh;
+mod leaky_relu;
+mod elu;
+mod gelu;
+mod swish;
+mod softplus;
 mod softmax;
 mod avgpool2d;
 mod maxpool2d;
 mod zeropadding2d;
 mod conv2d;
 mod flatten;
+mod dropout;
+mod batchnorm;
 
 pub use self::linear::*;
 pub use self::sigmoid::*;
 pub use self::chain::*;
 pub use self::relu::*;
+pub use self::tanh::*;
+pub use self::leaky_relu::*;
+pub use self::elu::*;
+pub use self::gelu::*;
+pub use self::swish::*;
+pub use self::softplus::*;
 pub use self::softmax::*;
 pub use self::conv2d::*;
 pub use self::avgpool2d::*;
 pub use self::maxpool2d::*;
 pub use self::flatten::*;
-pub use self::zeropadding2d::*;
\ No newline at end of file
+pub use self::zeropadding2d::*;
+pub use self::dropout::*;
+pub use self::batchnorm::*;
\ No newline at end of file
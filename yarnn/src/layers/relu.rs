@@ -1,5 +1,5 @@
 use crate::tensor::{Tensor, TensorShape};
-use crate::backend::{Backend, BackendReLu};
+use crate::backend::{Backend, BackendActivation, Activation};
 use crate::layer::{Layer, LayerExt, DefaultLayerContext};
 use crate::optimizer::Optimizer;
 use core::marker::PhantomData;
@@ -15,7 +15,7 @@ pub struct ReLu<N, B>
 }
 
 impl <N, B, O> Layer<N, B, O> for ReLu<N, B> 
-    where B: Backend<N> + BackendReLu<N>,
+    where B: Backend<N> + BackendActivation<N>,
           O: Optimizer<N, B>
 {
     type Context = DefaultLayerContext<N, B>;
@@ -33,19 +33,19 @@ impl <N, B, O> Layer<N, B, O> for ReLu<N, B>
     fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
         ctx.update_outputs_shape(x.shape().get(0), &self.input_shape);
         
-        backend.relu(&mut ctx.outputs, x);
+        backend.activation(Activation::ReLu, &mut ctx.outputs, x);
     }
 
     #[inline]
-    fn backward(&mut self, backend: &B, dy: &B::Tensor, _: &B::Tensor, ctx: &mut Self::Context) {
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
         ctx.update_deltas_shape(dy.shape().get(0), &self.input_shape);
         
-        backend.relu_grad(&mut ctx.deltas, &ctx.outputs, dy);
+        backend.activation_grad(Activation::ReLu, &mut ctx.deltas, x, &ctx.outputs, dy);
     }
 }
 
 impl <N, B, O> LayerExt<N, B, O> for ReLu<N, B> 
-    where B: Backend<N> + BackendReLu<N>,
+    where B: Backend<N> + BackendActivation<N>,
           O: Optimizer<N, B>
 {
     type Config = ReLuConfig;
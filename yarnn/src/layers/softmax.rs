@@ -40,7 +40,7 @@ impl <N, B, O> Layer<N, B, O> for Softmax<N, B>
     fn backward(&mut self, backend: &B, dy: &B::Tensor, _: &B::Tensor, ctx: &mut Self::Context) {
         ctx.update_deltas_shape(dy.shape().get(0), &self.input_shape);
 
-        backend.copy(&mut ctx.deltas, dy);
+        backend.softmax_grad(&mut ctx.deltas, &ctx.outputs, dy);
     }
 }
 
@@ -0,0 +1,60 @@
+use crate::tensor::{Tensor, TensorShape};
+use crate::backend::{Backend, BackendActivation, Activation};
+use crate::layer::{Layer, LayerExt, DefaultLayerContext};
+use crate::optimizer::Optimizer;
+use core::marker::PhantomData;
+
+#[derive(Default)]
+pub struct SwishConfig;
+
+/// Swish (SiLU): `x * sigmoid(x)`
+pub struct Swish<N, B> 
+    where B: Backend<N>,
+{
+    input_shape: TensorShape,
+    _x: PhantomData<fn(N, B)>,
+}
+
+impl <N, B, O> Layer<N, B, O> for Swish<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Context = DefaultLayerContext<N, B>;
+
+    fn name(&self) -> &str {
+        "Swish"
+    }
+    
+    #[inline]
+    fn input_shape(&self) -> TensorShape {
+        self.input_shape.clone()
+    }
+    
+    #[inline]
+    fn forward(&self, backend: &B, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_outputs_shape(x.shape().get(0), &self.input_shape);
+        
+        backend.activation(Activation::Swish, &mut ctx.outputs, x);
+    }
+
+    #[inline]
+    fn backward(&mut self, backend: &B, dy: &B::Tensor, x: &B::Tensor, ctx: &mut Self::Context) {
+        ctx.update_deltas_shape(dy.shape().get(0), &self.input_shape);
+        
+        backend.activation_grad(Activation::Swish, &mut ctx.deltas, x, &ctx.outputs, dy);
+    }
+}
+
+impl <N, B, O> LayerExt<N, B, O> for Swish<N, B> 
+    where B: Backend<N> + BackendActivation<N>,
+          O: Optimizer<N, B>
+{
+    type Config = SwishConfig;
+
+    fn create(input_shape: TensorShape, _cfg: Self::Config) -> Self {
+        Swish {
+            input_shape,
+            _x: Default::default()
+        }
+    }
+}
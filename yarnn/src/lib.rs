@@ -1,4 +1,5 @@
 #![feature(specialization, trait_alias)]
+#![cfg_attr(test, feature(test))]
 #![recursion_limit="128"]
 
 pub mod layer;
@@ -8,13 +9,20 @@ pub mod optimizer;
 pub mod optimizers;
 
 pub mod backend;
+pub mod activation;
 pub mod native;
 
 pub mod loss;
 pub mod losses;
 
+pub mod model;
+pub mod network;
+pub mod metrics;
+pub mod data;
+
 pub mod tensor;
 pub mod params;
+pub mod serialize;
 
 #[macro_use]
 mod macros;
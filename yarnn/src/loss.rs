@@ -1,6 +1,18 @@
-use crate::backend::Backend;
+use crate::backend::{Backend, BackendReduce};
+use crate::tensor::Tensor;
 
 pub trait Loss<N, B: Backend<N>> {
     fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor);
     fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor);
-}
\ No newline at end of file
+
+    /// Loss averaged over the batch, `dst` holds the per element losses afterwards
+    fn batch_loss(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) -> f32
+        where B: BackendReduce<N>
+    {
+        self.compute(backend, dst, pred, target);
+
+        let sum = backend.scalar_to_f64(backend.sum(dst));
+
+        (sum / pred.shape().get(0) as f64) as f32
+    }
+}
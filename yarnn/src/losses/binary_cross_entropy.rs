@@ -0,0 +1,39 @@
+use crate::loss::Loss;
+use crate::backend::{Backend, BackendBinaryCrossEntropy};
+use core::marker::PhantomData;
+
+
+/// Binary cross entropy of independent outputs against `0..1` targets.
+/// With `from_logits` the predictions are raw scores, and the sigmoid is fused into
+/// the loss (the model must not end with a `Sigmoid` layer then), otherwise they are probabilities.
+pub struct BinaryCrossEntropyLoss<N, B> {
+    from_logits: bool,
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> BinaryCrossEntropyLoss<N, B> {
+    pub fn new(from_logits: bool) -> Self {
+        Self {
+            from_logits,
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendBinaryCrossEntropy<N>> Loss<N, B> for BinaryCrossEntropyLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        if self.from_logits {
+            backend.sigmoid_binary_cross_entropy(dst, pred, target);
+        } else {
+            backend.binary_cross_entropy(dst, pred, target);
+        }
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        if self.from_logits {
+            backend.sigmoid_binary_cross_entropy_grad(dst, pred, target);
+        } else {
+            backend.binary_cross_entropy_grad(dst, pred, target);
+        }
+    }
+}
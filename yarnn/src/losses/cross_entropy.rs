@@ -1,9 +1,9 @@
 use crate::loss::Loss;
-use crate::backend::{Backend, BackendSub};
+use crate::backend::{Backend, BackendAxpy, BackendCrossEntropy, BackendSoftmax};
 use core::marker::PhantomData;
 
 
-
+/// Categorical cross entropy over predicted probabilities, e.g. the outputs of `Softmax`.
 pub struct CrossEntropyLoss<N, B> {
     _m: PhantomData<fn(N, B)>   
 }
@@ -16,12 +16,51 @@ impl<N, B> CrossEntropyLoss<N, B> {
     }
 }
 
-impl<N, B: Backend<N> + BackendSub<N>> Loss<N, B> for CrossEntropyLoss<N, B> {
-    fn compute(&self, _backend: &B, _dst: &mut B::Tensor, _pred: &B::Tensor, _target: &B::Tensor) {
-        // TODO
+impl<N, B> Default for CrossEntropyLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, B: Backend<N> + BackendCrossEntropy<N>> Loss<N, B> for CrossEntropyLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.cross_entropy(dst, pred, target);
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {      
+        backend.cross_entropy_grad(dst, pred, target);
+    }
+}
+
+/// Softmax fused with categorical cross entropy, takes raw logits so the model
+/// must not end with a `Softmax` layer. The gradient is simply `softmax(pred) - target`.
+pub struct SoftmaxCrossEntropyLoss<N, B> {
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> SoftmaxCrossEntropyLoss<N, B> {
+    pub fn new() -> Self {
+        Self {
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B> Default for SoftmaxCrossEntropyLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, B> Loss<N, B> for SoftmaxCrossEntropyLoss<N, B> 
+    where B: Backend<N> + BackendSoftmax<N> + BackendAxpy<N> + BackendCrossEntropy<N>
+{
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.softmax_cross_entropy(dst, pred, target);
     }
 
     fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {      
-        backend.sub(dst, pred, target);
+        backend.softmax(dst, pred);
+        backend.axpy(dst, backend.scalar_f32(-1.0), target);
     }
-}
\ No newline at end of file
+}
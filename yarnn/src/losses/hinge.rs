@@ -0,0 +1,34 @@
+use crate::loss::Loss;
+use crate::backend::{Backend, BackendHinge};
+use core::marker::PhantomData;
+
+
+/// Multi-class hinge loss over raw scores and one-hot targets,
+/// every other class is pushed at least a margin of 1 below the true one.
+pub struct HingeLoss<N, B> {
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> HingeLoss<N, B> {
+    pub fn new() -> Self {
+        Self {
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B> Default for HingeLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, B: Backend<N> + BackendHinge<N>> Loss<N, B> for HingeLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.hinge(dst, pred, target);
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.hinge_grad(dst, pred, target);
+    }
+}
@@ -0,0 +1,32 @@
+use crate::loss::Loss;
+use crate::backend::{Backend, BackendHuber};
+use core::marker::PhantomData;
+
+
+/// Quadratic for errors up to `delta` and linear beyond, so outliers weigh less than with MSE.
+/// SmoothL1 is the same loss divided by `delta`.
+pub struct HuberLoss<N, B> {
+    delta: f32,
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> HuberLoss<N, B> {
+    pub fn new(delta: f32) -> Self {
+        assert!(delta > 0.0);
+
+        Self {
+            delta,
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendHuber<N>> Loss<N, B> for HuberLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.huber(dst, pred, target, backend.scalar_f32(self.delta));
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.huber_grad(dst, pred, target, backend.scalar_f32(self.delta));
+    }
+}
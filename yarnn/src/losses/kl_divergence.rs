@@ -0,0 +1,33 @@
+use crate::loss::Loss;
+use crate::backend::{Backend, BackendKlDivergence};
+use core::marker::PhantomData;
+
+
+/// Kullback-Leibler divergence of predicted probabilities from the target distribution.
+pub struct KlDivergenceLoss<N, B> {
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> KlDivergenceLoss<N, B> {
+    pub fn new() -> Self {
+        Self {
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B> Default for KlDivergenceLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, B: Backend<N> + BackendKlDivergence<N>> Loss<N, B> for KlDivergenceLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.kl_divergence(dst, pred, target);
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.kl_divergence_grad(dst, pred, target);
+    }
+}
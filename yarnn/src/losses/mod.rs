@@ -1,5 +1,116 @@
 mod mse;
 mod cross_entropy;
+mod binary_cross_entropy;
+mod huber;
+mod hinge;
+mod kl_divergence;
+mod nll;
 
 pub use self::mse::*;
-pub use self::cross_entropy::*;
\ No newline at end of file
+pub use self::cross_entropy::*;
+pub use self::binary_cross_entropy::*;
+pub use self::huber::*;
+pub use self::hinge::*;
+pub use self::kl_divergence::*;
+pub use self::nll::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{Backend, BackendReduce};
+    use crate::loss::Loss;
+    use crate::native::{Native, NativeTensor};
+    use crate::tensor::Tensor;
+
+    type B = Native<f32>;
+
+    /// Compares `derivative` with central differences of the summed `compute`
+    fn check_grad<L: Loss<f32, B>>(name: &str, loss: &L, pred: &[f32], target: &[f32]) {
+        let bac = B::default();
+        let mut p = NativeTensor::new((2, 3));
+        let mut t = NativeTensor::new((2, 3));
+        let mut dst = NativeTensor::new((2, 3));
+        let mut grad = NativeTensor::new((2, 3));
+
+        bac.load_tensor_f32(&mut p, pred);
+        bac.load_tensor_f32(&mut t, target);
+
+        loss.derivative(&bac, &mut grad, &p, &t);
+
+        let mut objective = |vals: &[f32]| -> f32 {
+            bac.load_tensor_f32(&mut p, vals);
+            loss.compute(&bac, &mut dst, &p, &t);
+
+            dst.read().iter().sum()
+        };
+
+        let h = 1e-3;
+
+        for i in 0 .. 6 {
+            let mut pp = pred.to_vec();
+            let mut pm = pred.to_vec();
+
+            pp[i] += h;
+            pm[i] -= h;
+
+            let numeric = (objective(&pp) - objective(&pm)) / (2.0 * h);
+
+            assert!((numeric - grad.read()[i]).abs() < 1e-2, "{} grad[{}]: {} != {}", name, i, grad.read()[i], numeric);
+        }
+    }
+
+    #[test]
+    fn test_losses_grad() {
+        let probs = [0.2, 0.3, 0.5, 0.6, 0.1, 0.3];
+        let logits = [0.5, -1.2, 2.0, 0.1, 0.7, -0.3];
+        let one_hot = [0.0, 0.0, 1.0, 1.0, 0.0, 0.0];
+        let soft = [0.1, 0.1, 0.8, 0.5, 0.25, 0.25];
+
+        check_grad("bce", &BinaryCrossEntropyLoss::new(false), &probs, &[0.0, 1.0, 1.0, 0.5, 0.0, 1.0]);
+        check_grad("bce logits", &BinaryCrossEntropyLoss::new(true), &logits, &[0.0, 1.0, 1.0, 0.5, 0.0, 1.0]);
+        check_grad("huber", &HuberLoss::new(1.0), &logits, &[2.0, -1.0, -0.5, 0.0, 0.0, 1.5]);
+        check_grad("hinge", &HingeLoss::new(), &logits, &one_hot);
+        check_grad("kl", &KlDivergenceLoss::new(), &probs, &soft);
+        check_grad("nll", &NllLoss::new(), &[-1.6, -1.2, -0.7, -0.5, -2.3, -1.2], &one_hot);
+    }
+
+    #[test]
+    fn test_losses() {
+        let bac = B::default();
+        let mut p = NativeTensor::new((2, 3));
+        let mut t = NativeTensor::new((2, 3));
+        let mut dst = NativeTensor::new((2, 3));
+        let mut fused = NativeTensor::new((2, 3));
+
+        // sigmoid(logits) fed to the plain loss has to match the fused one
+        let logits = [0.5, -1.2, 2.0, 0.1, 0.7, -0.3];
+        let probs: Vec<f32> = logits.iter().map(|x: &f32| 1.0 / (1.0 + (-x).exp())).collect();
+
+        bac.load_tensor_f32(&mut t, &[0.0, 1.0, 1.0, 0.5, 0.0, 1.0]);
+        bac.load_tensor_f32(&mut p, &logits);
+        BinaryCrossEntropyLoss::new(true).compute(&bac, &mut fused, &p, &t);
+        bac.load_tensor_f32(&mut p, &probs);
+        BinaryCrossEntropyLoss::new(false).compute(&bac, &mut dst, &p, &t);
+
+        for i in 0 .. 6 {
+            assert!((dst.read()[i] - fused.read()[i]).abs() < 1e-5);
+        }
+
+        bac.load_tensor_f32(&mut p, &[0.5, 3.0, -0.5, 0.0, 0.0, 0.0]);
+        bac.load_tensor_f32(&mut t, &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        HuberLoss::new(1.0).compute(&bac, &mut dst, &p, &t);
+        assert_eq!(&dst.read()[0 .. 3], &[0.125, 2.5, 0.125]);
+
+        // only classes within the margin of the true one count: 0.5 for the first row, 1.6 + 0.6 for the second
+        bac.load_tensor_f32(&mut p, &[0.5, -1.2, 1.0, 0.1, 0.7, -0.3]);
+        bac.load_tensor_f32(&mut t, &[0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        HingeLoss::new().compute(&bac, &mut dst, &p, &t);
+        assert!((bac.sum(&dst) - 2.7).abs() < 1e-6);
+
+        // identical distributions don't diverge
+        bac.load_tensor_f32(&mut p, &[0.1, 0.1, 0.8, 0.5, 0.5, 0.0]);
+        bac.load_tensor_f32(&mut t, &[0.1, 0.1, 0.8, 0.5, 0.5, 0.0]);
+        KlDivergenceLoss::new().compute(&bac, &mut dst, &p, &t);
+        assert!(bac.sum(&dst).abs() < 1e-6);
+    }
+}
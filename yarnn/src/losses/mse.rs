@@ -17,13 +17,17 @@ impl<N, B> MeanSquareErrorLoss<N, B> {
     }
 }
 
+impl<N, B> Default for MeanSquareErrorLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<N, B> Loss<N, B> for MeanSquareErrorLoss<N, B> 
     where B: Backend<N> + BackendMse<N>
 {
     fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
-        let batch_size = pred.shape().get(0) as f32;
-
-        backend.scaled_square_diff(dst, target, pred, backend.scalar_f32(0.5 * batch_size));
+        backend.scaled_square_diff(dst, target, pred, backend.scalar_f32(0.5));
     }
 
     fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
@@ -0,0 +1,33 @@
+use crate::loss::Loss;
+use crate::backend::{Backend, BackendNll};
+use core::marker::PhantomData;
+
+
+/// Negative log likelihood over predicted log probabilities and one-hot targets.
+pub struct NllLoss<N, B> {
+    _m: PhantomData<fn(N, B)>   
+}
+
+impl<N, B> NllLoss<N, B> {
+    pub fn new() -> Self {
+        Self {
+            _m: Default::default()
+        }
+    }
+}
+
+impl<N, B> Default for NllLoss<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, B: Backend<N> + BackendNll<N>> Loss<N, B> for NllLoss<N, B> {
+    fn compute(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.nll(dst, pred, target);
+    }
+
+    fn derivative(&self, backend: &B, dst: &mut B::Tensor, pred: &B::Tensor, target: &B::Tensor) {
+        backend.nll_grad(dst, pred, target);
+    }
+}
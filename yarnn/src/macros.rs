@@ -83,12 +83,12 @@ macro_rules! sequential_type_ctx {
 #[macro_export]
 macro_rules! sequential_type_ctx_impl {
     ($t:ty {$($xx:tt)*}) => {
-        $crate::layer::DefaultLayerContext<N, B>
+        <$t as $crate::layer::Layer<N, B, O>>::Context
     };
 
     ($t:ty {$($xx:tt)*}, $($tt:tt)*) => {
         $crate::layers::ChainContext<N, B,
-            $crate::layer::DefaultLayerContext<N, B>,
+            <$t as $crate::layer::Layer<N, B, O>>::Context,
             $crate::sequential_type_ctx_impl!($($tt)*)
         >
     };
@@ -108,7 +108,7 @@ macro_rules! model_impl {
         mod ctx {
             #[allow(unused_imports)]
             use super::*;
-            pub type $name<N, B> = $crate::sequential_type_ctx!($($tt)*);
+            pub type $name<N, B, O> = $crate::sequential_type_ctx!($($tt)*);
         }
 
         pub struct $name <N, B, O>
@@ -155,7 +155,7 @@ macro_rules! model_impl {
             where B: $crate::backend::Backend<N> + $trait,
                   O: $crate::optimizer::Optimizer<N, B>
         {
-            type Context = ctx::$name<N, B>;
+            type Context = ctx::$name<N, B, O>;
 
             #[inline]
             fn name(&self) -> &str {
@@ -202,6 +202,11 @@ macro_rules! model_impl {
                 self.inner.optimize(backend, optimizer);
             }
 
+            #[inline]
+            fn visit_params<V: $crate::params::ParamsVisitor<N, B, O>>(&mut self, visitor: &mut V) {
+                self.inner.visit_params(visitor);
+            }
+
             fn fmt(&self, f: &mut core::fmt::Formatter, padding: usize) -> core::fmt::Result {
                 writeln!(f, "{}{}[{}] {{",  "", self.name(), self.param_count())?;
                 self.inner.fmt(f, padding + 2)?;
@@ -227,11 +232,13 @@ macro_rules! model_impl {
 macro_rules! model {
     ($name:ident ($($init:tt)*) { $($tt:tt)* }) => {
         mod tmp {
-            pub trait BackendDefault<N> = $crate::backend::BackendReLu<N> 
+            pub trait BackendDefault<N> = $crate::backend::BackendActivation<N> 
                   + $crate::backend::BackendBias<N>
+                  + $crate::backend::BackendBias2d<N>
                   + $crate::backend::BackendScale<N>
-                  + $crate::backend::BackendSigmoid<N>
                   + $crate::backend::BackendSoftmax<N>
+                  + $crate::backend::BackendDropout<N>
+                  + $crate::backend::BackendBatchNorm<N>
                   + $crate::backend::BackendGemm<N>
                   + $crate::backend::BackendConv2d<N>
                   + $crate::backend::BackendMaxPool2d<N>
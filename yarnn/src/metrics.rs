@@ -0,0 +1,246 @@
+use crate::backend::Backend;
+use crate::tensor::Tensor;
+
+use core::fmt;
+
+
+/// Counts of (actual, predicted) class pairs of a single label classifier
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConfusionMatrix {
+    classes: usize,
+    counts: Vec<u32>,
+}
+
+impl ConfusionMatrix {
+    pub fn new(classes: usize) -> Self {
+        Self {
+            classes,
+            counts: vec![0; classes * classes],
+        }
+    }
+
+    /// Builds the matrix from `(batch, classes)` scores, the predicted class is the argmax of each row
+    pub fn from_scores<L: Copy + Into<u32>>(scores: &[f32], classes: usize, labels: &[L]) -> Self {
+        assert_eq!(scores.len(), labels.len() * classes);
+
+        let mut matrix = Self::new(classes);
+
+        for (row, &label) in scores.chunks(classes).zip(labels.iter()) {
+            matrix.add(label.into() as usize, argmax(row));
+        }
+
+        matrix
+    }
+
+    /// Builds the matrix from a `(batch, classes)` prediction tensor
+    pub fn from_tensor<N, B, L>(backend: &B, pred: &B::Tensor, labels: &[L]) -> Self
+        where B: Backend<N>,
+              L: Copy + Into<u32>,
+    {
+        let mut scores = vec![0.0; pred.shape().size()];
+        backend.store_tensor_f32(pred, &mut scores);
+
+        Self::from_scores(&scores, pred.shape().last_axis() as usize, labels)
+    }
+
+    #[inline]
+    pub fn add(&mut self, actual: usize, predicted: usize) {
+        self.counts[actual * self.classes + predicted] += 1;
+    }
+
+    #[inline]
+    pub fn classes(&self) -> usize {
+        self.classes
+    }
+
+    #[inline]
+    pub fn count(&self, actual: usize, predicted: usize) -> u32 {
+        self.counts[actual * self.classes + predicted]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    pub fn true_positives(&self, class: usize) -> u32 {
+        self.count(class, class)
+    }
+
+    /// Samples predicted as `class`
+    pub fn predicted(&self, class: usize) -> u32 {
+        (0 .. self.classes).map(|actual| self.count(actual, class)).sum()
+    }
+
+    /// Samples labeled as `class`
+    pub fn actual(&self, class: usize) -> u32 {
+        self.counts[class * self.classes .. (class + 1) * self.classes].iter().sum()
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        let correct: u32 = (0 .. self.classes).map(|class| self.true_positives(class)).sum();
+
+        ratio(correct, self.total())
+    }
+
+    pub fn precision(&self, class: usize) -> f32 {
+        ratio(self.true_positives(class), self.predicted(class))
+    }
+
+    pub fn recall(&self, class: usize) -> f32 {
+        ratio(self.true_positives(class), self.actual(class))
+    }
+
+    pub fn f1(&self, class: usize) -> f32 {
+        f1(self.precision(class), self.recall(class))
+    }
+
+    /// Unweighted mean of the per class precisions
+    pub fn macro_precision(&self) -> f32 {
+        self.macro_average(Self::precision)
+    }
+
+    /// Unweighted mean of the per class recalls
+    pub fn macro_recall(&self) -> f32 {
+        self.macro_average(Self::recall)
+    }
+
+    /// Unweighted mean of the per class F1 scores
+    pub fn macro_f1(&self) -> f32 {
+        self.macro_average(Self::f1)
+    }
+
+    /// Precision over the pooled counts of all classes.
+    /// Every sample has exactly one label and one prediction, so the micro precision, recall
+    /// and F1 all equal the accuracy.
+    pub fn micro_precision(&self) -> f32 {
+        let tp: u32 = (0 .. self.classes).map(|class| self.true_positives(class)).sum();
+        let predicted: u32 = (0 .. self.classes).map(|class| self.predicted(class)).sum();
+
+        ratio(tp, predicted)
+    }
+
+    pub fn micro_recall(&self) -> f32 {
+        let tp: u32 = (0 .. self.classes).map(|class| self.true_positives(class)).sum();
+        let actual: u32 = (0 .. self.classes).map(|class| self.actual(class)).sum();
+
+        ratio(tp, actual)
+    }
+
+    pub fn micro_f1(&self) -> f32 {
+        f1(self.micro_precision(), self.micro_recall())
+    }
+
+    fn macro_average(&self, metric: fn(&Self, usize) -> f32) -> f32 {
+        if self.classes == 0 {
+            return 0.0;
+        }
+
+        (0 .. self.classes).map(|class| metric(self, class)).sum::<f32>() / self.classes as f32
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.counts.chunks(self.classes) {
+            for count in row {
+                write!(f, "{:>6}", count)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Fraction of samples whose label is among the `k` highest of their `(batch, classes)` scores
+pub fn top_k_accuracy<L: Copy + Into<u32>>(scores: &[f32], classes: usize, labels: &[L], k: usize) -> f32 {
+    assert_eq!(scores.len(), labels.len() * classes);
+
+    let hits = scores.chunks(classes)
+        .zip(labels.iter())
+        .filter(|(row, &label)| {
+            let label = label.into() as usize;
+            let higher = row.iter().filter(|&&score| score > row[label]).count();
+
+            higher < k
+        })
+        .count();
+
+    ratio(hits as u32, labels.len() as u32)
+}
+
+fn argmax(row: &[f32]) -> usize {
+    let mut max = 0;
+
+    for (idx, &val) in row.iter().enumerate() {
+        if val > row[max] {
+            max = idx;
+        }
+    }
+
+    max
+}
+
+#[inline]
+fn ratio(num: u32, den: u32) -> f32 {
+    if den == 0 {
+        0.0
+    } else {
+        num as f32 / den as f32
+    }
+}
+
+#[inline]
+fn f1(precision: f32, recall: f32) -> f32 {
+    if precision + recall == 0.0 {
+        0.0
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_confusion_matrix() {
+        let scores = [
+            0.8, 0.1, 0.1,
+            0.2, 0.7, 0.1,
+            0.1, 0.6, 0.3,
+            0.3, 0.3, 0.4,
+            0.5, 0.4, 0.1,
+        ];
+        let labels = [0u8, 1, 2, 2, 1];
+
+        let matrix = ConfusionMatrix::from_scores(&scores, 3, &labels);
+
+        assert_eq!(matrix.total(), 5);
+        assert_eq!(matrix.count(2, 1), 1);
+        assert_eq!(matrix.count(1, 0), 1);
+        assert_close(matrix.accuracy(), 0.6);
+
+        assert_close(matrix.precision(0), 0.5);
+        assert_close(matrix.recall(0), 1.0);
+        assert_close(matrix.f1(0), 2.0 / 3.0);
+        assert_close(matrix.precision(1), 0.5);
+        assert_close(matrix.recall(1), 0.5);
+        assert_close(matrix.precision(2), 1.0);
+        assert_close(matrix.recall(2), 0.5);
+
+        assert_close(matrix.macro_precision(), 2.0 / 3.0);
+        assert_close(matrix.macro_recall(), 2.0 / 3.0);
+        assert_close(matrix.macro_f1(), (2.0 / 3.0 + 0.5 + 2.0 / 3.0) / 3.0);
+        assert_close(matrix.micro_f1(), matrix.accuracy());
+
+        assert_close(top_k_accuracy(&scores, 3, &labels, 1), 0.6);
+        assert_close(top_k_accuracy(&scores, 3, &labels, 2), 1.0);
+        assert_close(ConfusionMatrix::new(3).macro_f1(), 0.0);
+    }
+}
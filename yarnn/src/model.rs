@@ -1,42 +1,118 @@
+use crate::backend::{Backend, BackendReduce};
+use crate::layer::{Layer, LayerContext};
+use crate::loss::Loss;
+use crate::optimizer::Optimizer;
+use crate::params::LayerGrads;
+use crate::tensor::Tensor;
 
-pub trait Model<N, B, O> 
+use core::marker::PhantomData;
+
+
+pub trait Model<N, B, O>
     where B: Backend<N>,
           O: Optimizer<N, B>
 {
     fn init(&mut self, backend: &B);
-    fn predict(&mut self, backend: &B, x: &B::Tensor);
-    fn evaluate(&mut self, backend: &B, x: &B::Tensor, y: &B::Tensor) -> ConfusionMatrix;
-    fn train(&mut self, backend: &B, optimizer: &O, x: &B::Tensor, y: &B::Tensor);
+
+    /// Runs inference over the batch `x`
+    fn predict(&mut self, backend: &B, x: &B::Tensor) -> &B::Tensor;
+
+    /// Mean loss of the predictions for the batch `x` against targets `y`
+    fn evaluate<LS: Loss<N, B>>(&mut self, backend: &B, loss: &LS, x: &B::Tensor, y: &B::Tensor) -> f32
+        where B: BackendReduce<N>;
+
+    /// Single training step over the batch `x` with targets `y`
+    fn train<LS: Loss<N, B>>(&mut self, backend: &B, optimizer: &O, loss: &LS, x: &B::Tensor, y: &B::Tensor);
 }
 
-pub struct DefaultModel<N, B, O, L> 
+pub struct DefaultModel<N, B, O, L>
     where B: Backend<N>,
           O: Optimizer<N, B>,
-          L: AbstractLayer<N, B, O>
+          L: Layer<N, B, O>
 {
     inner: L,
     train_ctx: L::Context,
     evaluate_ctx: L::Context,
+    deltas: B::Tensor,
+    losses: B::Tensor,
+    _m: PhantomData<fn(N, B, O)>,
 }
 
-impl<N, B, O, L> Model<N, B, O> for DefaultModel<N, B, O, L> 
+impl<N, B, O, L> DefaultModel<N, B, O, L>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>
+{
+    pub fn new(inner: L) -> Self {
+        let mut evaluate_ctx = L::Context::default();
+        evaluate_ctx.set_training(false);
+
+        Self {
+            inner,
+            train_ctx: Default::default(),
+            evaluate_ctx,
+            deltas: B::Tensor::new(()),
+            losses: B::Tensor::new(()),
+            _m: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub fn layer(&self) -> &L {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn layer_mut(&mut self) -> &mut L {
+        &mut self.inner
+    }
 
+    #[inline]
+    pub fn into_layer(self) -> L {
+        self.inner
+    }
+}
+
+impl<N, B, O, L> Model<N, B, O> for DefaultModel<N, B, O, L>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>
 {
     fn init(&mut self, backend: &B) {
-
+        self.inner.init(backend);
     }
 
     fn predict(&mut self, backend: &B, x: &B::Tensor) -> &B::Tensor {
-        self.inner.forward(backend, x, self.evaluate_ctx);
+        self.inner.forward(backend, x, &mut self.evaluate_ctx);
 
         self.evaluate_ctx.outputs()
     }
 
-    fn evaluate(&mut self, backend: &B, x: &B::Tensor, y: &B::Tensor) -> ConfusionMatrix {
-        
+    fn evaluate<LS: Loss<N, B>>(&mut self, backend: &B, loss: &LS, x: &B::Tensor, y: &B::Tensor) -> f32
+        where B: BackendReduce<N>
+    {
+        self.inner.forward(backend, x, &mut self.evaluate_ctx);
+
+        if self.losses.shape() != y.shape() {
+            self.losses.resize(y.shape().clone());
+        }
+
+        loss.batch_loss(backend, &mut self.losses, self.evaluate_ctx.outputs(), y)
     }
 
-    fn train(&mut self, backend: &B, optimizer: &O, x: &B::Tensor, y: &B::Tensor) {
-        
+    fn train<LS: Loss<N, B>>(&mut self, backend: &B, optimizer: &O, loss: &LS, x: &B::Tensor, y: &B::Tensor) {
+        self.inner.forward(backend, x, &mut self.train_ctx);
+
+        if self.deltas.shape() != y.shape() {
+            self.deltas.resize(y.shape().clone());
+        }
+
+        loss.derivative(backend, &mut self.deltas, self.train_ctx.outputs(), y);
+
+        self.inner.backward(backend, &self.deltas, x, &mut self.train_ctx);
+        self.inner.calc_gradients(backend, &self.deltas, x, &mut self.train_ctx);
+
+        optimizer.prepare(backend, &mut LayerGrads::new(&mut self.inner));
+        self.inner.optimize(backend, optimizer);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,252 @@
+use super::NativeNumber;
+
+pub fn batch_norm_stats<N: NativeNumber>(mean: &mut [N], var: &mut [N], x: &[N],
+                        bs: usize, features: usize, inner: usize) {
+    let mean = &mut mean[0..features];
+    let var = &mut var[0..features];
+    let x = &x[0..bs * features * inner];
+    let count = N::from_usize(bs * inner);
+
+    for f in 0..features {
+        let mut sum = N::ZERO;
+
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                sum += x[offset + i];
+            }
+        }
+
+        let m = sum / count;
+        let mut sum_sq = N::ZERO;
+
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                let diff = x[offset + i] - m;
+                sum_sq += diff * diff;
+            }
+        }
+
+        mean[f] = m;
+        var[f] = sum_sq / count;
+    }
+}
+
+pub fn batch_norm<N: NativeNumber>(y: &mut [N], xhat: &mut [N], x: &[N],
+                  mean: &[N], var: &[N], gamma: &[N], beta: &[N], eps: N,
+                  bs: usize, features: usize, inner: usize) {
+    let size = bs * features * inner;
+    let y = &mut y[0..size];
+    let xhat = &mut xhat[0..size];
+    let x = &x[0..size];
+
+    for bi in 0..bs {
+        for f in 0..features {
+            let offset = (bi * features + f) * inner;
+            let inv_std = N::ONE / (var[f] + eps).sqrt();
+
+            for i in 0..inner {
+                let idx = offset + i;
+
+                xhat[idx] = (x[idx] - mean[f]) * inv_std;
+                y[idx] = gamma[f] * xhat[idx] + beta[f];
+            }
+        }
+    }
+}
+
+pub fn batch_norm_grad<N: NativeNumber>(dx: &mut [N], dy: &[N], xhat: &[N],
+                       var: &[N], gamma: &[N], eps: N,
+                       bs: usize, features: usize, inner: usize) {
+    let size = bs * features * inner;
+    let dx = &mut dx[0..size];
+    let dy = &dy[0..size];
+    let xhat = &xhat[0..size];
+    let count = N::from_usize(bs * inner);
+
+    for f in 0..features {
+        // sum(dxhat) and sum(dxhat * xhat), where dxhat = dy * gamma
+        let mut sum_dxhat = N::ZERO;
+        let mut sum_dxhat_xhat = N::ZERO;
+
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                let dxhat = dy[offset + i] * gamma[f];
+
+                sum_dxhat += dxhat;
+                sum_dxhat_xhat += dxhat * xhat[offset + i];
+            }
+        }
+
+        let inv_std = N::ONE / (var[f] + eps).sqrt();
+
+        // dx = inv_std / m * (m * dxhat - sum(dxhat) - xhat * sum(dxhat * xhat))
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                let idx = offset + i;
+                let dxhat = dy[idx] * gamma[f];
+
+                dx[idx] = inv_std / count * (count * dxhat - sum_dxhat - xhat[idx] * sum_dxhat_xhat);
+            }
+        }
+    }
+}
+
+/// Gradient through fixed statistics, as in inference: `dx = gamma / sqrt(var + eps) * dy`
+pub fn batch_norm_inference_grad<N: NativeNumber>(dx: &mut [N], dy: &[N],
+                                 var: &[N], gamma: &[N], eps: N,
+                                 bs: usize, features: usize, inner: usize) {
+    let size = bs * features * inner;
+    let dx = &mut dx[0..size];
+    let dy = &dy[0..size];
+
+    for f in 0..features {
+        let scale = gamma[f] / (var[f] + eps).sqrt();
+
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                dx[offset + i] = scale * dy[offset + i];
+            }
+        }
+    }
+}
+
+pub fn batch_norm_param_grads<N: NativeNumber>(dgamma: &mut [N], dbeta: &mut [N], dy: &[N], xhat: &[N],
+                              bs: usize, features: usize, inner: usize) {
+    let size = bs * features * inner;
+    let dgamma = &mut dgamma[0..features];
+    let dbeta = &mut dbeta[0..features];
+    let dy = &dy[0..size];
+    let xhat = &xhat[0..size];
+
+    for f in 0..features {
+        let mut sum_dy = N::ZERO;
+        let mut sum_dy_xhat = N::ZERO;
+
+        for bi in 0..bs {
+            let offset = (bi * features + f) * inner;
+
+            for i in 0..inner {
+                sum_dy += dy[offset + i];
+                sum_dy_xhat += dy[offset + i] * xhat[offset + i];
+            }
+        }
+
+        dgamma[f] = sum_dy_xhat;
+        dbeta[f] = sum_dy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_norm() {
+        // batch of 2, 2 channels of 2x1 images
+        let x: &[f32] = &[
+            1.0, 3.0,
+            0.0, 4.0,
+
+            5.0, 7.0,
+            2.0, 2.0,
+        ];
+
+        let gamma: &[f32] = &[1.0, 2.0];
+        let beta: &[f32] = &[0.0, 1.0];
+
+        let mean = &mut [0.0; 2];
+        let var = &mut [0.0; 2];
+        let y = &mut [0.0; 8];
+        let xhat = &mut [0.0; 8];
+
+        batch_norm_stats(mean, var, x, 2, 2, 2);
+
+        assert_eq!(mean, &[4.0, 2.0]);
+        assert_eq!(var, &[5.0, 2.0]);
+
+        batch_norm(y, xhat, x, mean, var, gamma, beta, 0.0, 2, 2, 2);
+
+        let s5 = 1.0 / 5.0f32.sqrt();
+        let s2 = 1.0 / 2.0f32.sqrt();
+
+        let expected: &[f32] = &[
+            -3.0 * s5, -s5,
+            1.0 - 4.0 * s2, 1.0 + 4.0 * s2,
+
+            1.0 * s5, 3.0 * s5,
+            1.0, 1.0,
+        ];
+
+        for i in 0..8 {
+            assert!((y[i] - expected[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_batch_norm_grad() {
+        let x: &[f32] = &[
+            0.3, -1.2, 0.5,
+            1.1,  0.4, -0.7,
+            -0.2, 2.0, 0.9,
+            0.8, -0.5, 0.1,
+        ];
+
+        // weights for a scalar objective L = sum(w * y)
+        let w: &[f32] = &[
+            0.1, -0.4, 0.7,
+            0.2,  0.5, -0.3,
+            -0.6, 0.3, 0.9,
+            0.4, -0.8, 0.2,
+        ];
+
+        let gamma: &[f32] = &[1.5, -0.5, 2.0];
+        let beta: &[f32] = &[0.1, 0.2, 0.3];
+        let eps = 1e-3;
+
+        let objective = |x: &[f32]| -> f64 {
+            let mean = &mut [0.0; 3];
+            let var = &mut [0.0; 3];
+            let y = &mut [0.0; 12];
+            let xhat = &mut [0.0; 12];
+
+            batch_norm_stats(mean, var, x, 4, 3, 1);
+            batch_norm(y, xhat, x, mean, var, gamma, beta, eps, 4, 3, 1);
+
+            y.iter().zip(w.iter()).map(|(&a, &b)| (a * b) as f64).sum()
+        };
+
+        let mean = &mut [0.0; 3];
+        let var = &mut [0.0; 3];
+        let y = &mut [0.0; 12];
+        let xhat = &mut [0.0; 12];
+        let dx = &mut [0.0; 12];
+
+        batch_norm_stats(mean, var, x, 4, 3, 1);
+        batch_norm(y, xhat, x, mean, var, gamma, beta, eps, 4, 3, 1);
+        batch_norm_grad(dx, w, xhat, var, gamma, eps, 4, 3, 1);
+
+        let h = 1e-2;
+
+        for i in 0..12 {
+            let mut xp = x.to_vec();
+            let mut xm = x.to_vec();
+
+            xp[i] += h;
+            xm[i] -= h;
+
+            let numeric = (objective(&xp) - objective(&xm)) / (2.0 * h as f64);
+
+            assert!((numeric - dx[i] as f64).abs() < 1e-2, "dx[{}]: {} != {}", i, dx[i], numeric);
+        }
+    }
+}
@@ -1,5 +1,6 @@
-#[allow(dead_code)]
-pub fn valid_conv2d_3x3(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
+use crate::native::NativeNumber;
+
+pub fn valid_conv2d_3x3<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
                         x_rows: isize, x_cols: isize, s_row: isize, s_col: isize) {
     
     let y_rows = (x_rows - 3) / s_row + 1;
@@ -12,7 +13,7 @@ pub fn valid_conv2d_3x3(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
     for y_y in 0..y_rows {
         for y_x in 0..y_cols {
             let mut xi = s_row * y_y * x_cols + s_col * y_x;            
-            let mut sum = 0.0;
+            let mut sum = N::ZERO;
 
             sum += x[(xi + 0) as usize] * w[0];
             sum += x[(xi + 1) as usize] * w[1];
@@ -36,10 +37,9 @@ pub fn valid_conv2d_3x3(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
 }
 
 
-#[allow(dead_code)]
-pub fn full_xcorr2d_3x3(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
-                        x_rows: isize, x_cols: isize, 
-                        s_row: isize, s_col: isize) {
+pub fn full_conv2d_3x3<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
+                       x_rows: isize, x_cols: isize, 
+                       s_row: isize, s_col: isize) {
     
     let y_cols = (x_cols - 1) * s_col + 3;
     let y_rows = (x_rows - 1) * s_row + 3;
@@ -53,24 +53,24 @@ pub fn full_xcorr2d_3x3(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
             let mut yi = s_row * x_y * y_cols + s_col * x_x;
             let z = alpha * x[(x_y * x_cols + x_x) as usize];
 
-            y[(yi + 0) as usize] += z * w[8];
-            y[(yi + 1) as usize] += z * w[7];
-            y[(yi + 2) as usize] += z * w[6];
+            y[(yi + 0) as usize] += z * w[0];
+            y[(yi + 1) as usize] += z * w[1];
+            y[(yi + 2) as usize] += z * w[2];
             yi += y_cols;
             
-            y[(yi + 0) as usize] += z * w[5];
+            y[(yi + 0) as usize] += z * w[3];
             y[(yi + 1) as usize] += z * w[4];
-            y[(yi + 2) as usize] += z * w[3];
+            y[(yi + 2) as usize] += z * w[5];
             yi += y_cols;
             
-            y[(yi + 0) as usize] += z * w[2];
-            y[(yi + 1) as usize] += z * w[1];
-            y[(yi + 2) as usize] += z * w[0];
+            y[(yi + 0) as usize] += z * w[6];
+            y[(yi + 1) as usize] += z * w[7];
+            y[(yi + 2) as usize] += z * w[8];
         }
     }
 }
 
-pub fn conv2d_forward_3x3(y: &mut [f32], x: &[f32], w: &[f32],
+pub fn conv2d_forward_3x3<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N],
                           bs: isize, x_channels: isize, y_channels: isize,
                           x_rows: isize, x_cols: isize, s_row: isize, s_col: isize) {
     
@@ -86,7 +86,7 @@ pub fn conv2d_forward_3x3(y: &mut [f32], x: &[f32], w: &[f32],
     
     let y = &mut y[0..(bs * y_batch_size) as usize];
     let x = &x[0..(bs * x_batch_size) as usize];
-    let w = &w[0..(y_channels * w_img_size) as usize];
+    let w = &w[0..(y_channels * x_channels * w_img_size) as usize];
     
     for bi in 0..bs {
         for x_ch in 0..x_channels {
@@ -97,17 +97,17 @@ pub fn conv2d_forward_3x3(y: &mut [f32], x: &[f32], w: &[f32],
                 let y_offset = (bi * y_batch_size + y_ch * y_img_size) as usize;
                 let y_img = &mut y[y_offset..y_offset + y_img_size as usize];
                 
-                let w_offset = (y_ch * w_img_size) as usize;
+                let w_offset = ((y_ch * x_channels + x_ch) * w_img_size) as usize;
                 let w = &w[w_offset..w_offset + w_img_size as usize];
                 
-                valid_conv2d_3x3(y_img, x_img, w, 1.0, x_rows, x_cols, s_row, s_col);
+                valid_conv2d_3x3(y_img, x_img, w, N::ONE, x_rows, x_cols, s_row, s_col);
             }
         }   
     }
 } 
 
 
-pub fn conv2d_backward_3x3(dx: &mut [f32], dy: &[f32], w: &[f32],
+pub fn conv2d_backward_3x3<N: NativeNumber>(dx: &mut [N], dy: &[N], w: &[N],
                        bs: isize, x_channels: isize, y_channels: isize,
                        y_rows: isize, y_cols: isize,
                        s_row: isize, s_col: isize) {
@@ -124,7 +124,7 @@ pub fn conv2d_backward_3x3(dx: &mut [f32], dy: &[f32], w: &[f32],
     
     let dx = &mut dx[0..(bs * dx_batch_size) as usize];
     let dy = &dy[0..(bs * dy_batch_size) as usize];
-    let w = &w[0..(y_channels * w_img_size) as usize];
+    let w = &w[0..(y_channels * x_channels * w_img_size) as usize];
     
     for bi in 0..bs {
         for y_ch in 0..y_channels {
@@ -135,10 +135,10 @@ pub fn conv2d_backward_3x3(dx: &mut [f32], dy: &[f32], w: &[f32],
                 let dx_offset = (bi * dx_batch_size + x_ch * dx_img_size) as usize;
                 let dx_img = &mut dx[dx_offset..dx_offset + dx_img_size as usize];
                 
-                let w_offset = (y_ch * w_img_size) as usize;
+                let w_offset = ((y_ch * x_channels + x_ch) * w_img_size) as usize;
                 let w = &w[w_offset..w_offset + w_img_size as usize];
                 
-                full_xcorr2d_3x3(dx_img, dy_img, w, 1.0, y_rows, y_cols, s_row, s_col);
+                full_conv2d_3x3(dx_img, dy_img, w, N::ONE, y_rows, y_cols, s_row, s_col);
             }
         }   
     }
@@ -4,8 +4,10 @@ mod kernel_5x5;
 pub use self::kernel_3x3::*;
 pub use self::kernel_5x5::*;
 
-#[allow(dead_code)]
-pub fn valid_conv2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
+use core::ops::Range;
+use super::NativeNumber;
+
+pub fn valid_conv2d<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
                     x_rows: isize, x_cols: isize, 
                     w_rows: isize, w_cols: isize, 
                     s_row: isize, s_col: isize) {
@@ -23,7 +25,7 @@ pub fn valid_conv2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
             let mut xi = s_row * y_y * x_cols + s_col * y_x;
             let mut wi = 0;
             
-            let mut sum = 0.0;
+            let mut sum = N::ZERO;
             for _ in 0..w_rows {
                 for w_x in 0..w_cols {
                     sum += x[(xi + w_x) as usize] * w[(wi + w_x) as usize];
@@ -38,8 +40,8 @@ pub fn valid_conv2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
     }
 }
 
-#[allow(dead_code)]
-pub fn valid_xcorr2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
+#[cfg(test)]
+pub fn valid_xcorr2d<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
                      x_rows: isize, x_cols: isize, 
                      w_rows: isize, w_cols: isize, 
                      s_row: isize, s_col: isize) {
@@ -57,7 +59,7 @@ pub fn valid_xcorr2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
             let mut xi = s_row * y_y * x_cols + s_col * y_x;
             let mut wi = w_rows * w_cols - 1;
             
-            let mut sum = 0.0;
+            let mut sum = N::ZERO;
             for _ in 0..w_rows {
                 for w_x in 0..w_cols {
                     sum += x[(xi + w_x) as usize] * w[(wi - w_x) as usize];
@@ -72,8 +74,7 @@ pub fn valid_xcorr2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
     }
 }
 
-#[allow(dead_code)]
-pub fn full_conv2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
+pub fn full_conv2d<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
                    x_rows: isize, x_cols: isize, 
                    w_rows: isize, w_cols: isize, 
                    s_row: isize, s_col: isize) {
@@ -103,8 +104,8 @@ pub fn full_conv2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
     }
 }
 
-#[allow(dead_code)]
-pub fn full_xcorr2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
+#[cfg(test)]
+pub fn full_xcorr2d<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N], alpha: N,
                     x_rows: isize, x_cols: isize, 
                     w_rows: isize, w_cols: isize, 
                     s_row: isize, s_col: isize) {
@@ -135,7 +136,7 @@ pub fn full_xcorr2d(y: &mut [f32], x: &[f32], w: &[f32], alpha: f32,
 }
 
 
-pub fn conv2d_forward(y: &mut [f32], x: &[f32], w: &[f32],
+pub fn conv2d_forward<N: NativeNumber>(y: &mut [N], x: &[N], w: &[N],
                   bs: isize, x_channels: isize, y_channels: isize,
                   x_rows: isize, x_cols: isize,
                   w_rows: isize, w_cols: isize,
@@ -153,7 +154,7 @@ pub fn conv2d_forward(y: &mut [f32], x: &[f32], w: &[f32],
     
     let y = &mut y[0..(bs * y_batch_size) as usize];
     let x = &x[0..(bs * x_batch_size) as usize];
-    let w = &w[0..(y_channels * w_img_size) as usize];
+    let w = &w[0..(y_channels * x_channels * w_img_size) as usize];
     
     for bi in 0..bs {
         for x_ch in 0..x_channels {
@@ -164,16 +165,16 @@ pub fn conv2d_forward(y: &mut [f32], x: &[f32], w: &[f32],
                 let y_offset = (bi * y_batch_size + y_ch * y_img_size) as usize;
                 let y_img = &mut y[y_offset..y_offset + y_img_size as usize];
                 
-                let w_offset = (y_ch * w_img_size) as usize;
+                let w_offset = ((y_ch * x_channels + x_ch) * w_img_size) as usize;
                 let w = &w[w_offset..w_offset + w_img_size as usize];
                 
-                valid_conv2d(y_img, x_img, w, 1.0, x_rows, x_cols, w_rows, w_cols, s_row, s_col);
+                valid_conv2d(y_img, x_img, w, N::ONE, x_rows, x_cols, w_rows, w_cols, s_row, s_col);
             }
         }   
     }
 } 
 
-pub fn conv2d_backward(dx: &mut [f32], dy: &[f32], w: &[f32],
+pub fn conv2d_backward<N: NativeNumber>(dx: &mut [N], dy: &[N], w: &[N],
                   bs: isize, x_channels: isize, y_channels: isize,
                   y_rows: isize, y_cols: isize,
                   w_rows: isize, w_cols: isize,
@@ -191,7 +192,7 @@ pub fn conv2d_backward(dx: &mut [f32], dy: &[f32], w: &[f32],
     
     let dx = &mut dx[0..(bs * dx_batch_size) as usize];
     let dy = &dy[0..(bs * dy_batch_size) as usize];
-    let w = &w[0..(y_channels * w_img_size) as usize];
+    let w = &w[0..(y_channels * x_channels * w_img_size) as usize];
     
     for bi in 0..bs {
         for y_ch in 0..y_channels {
@@ -202,24 +203,24 @@ pub fn conv2d_backward(dx: &mut [f32], dy: &[f32], w: &[f32],
                 let dx_offset = (bi * dx_batch_size + x_ch * dx_img_size) as usize;
                 let dx_img = &mut dx[dx_offset..dx_offset + dx_img_size as usize];
                 
-                let w_offset = (y_ch * w_img_size) as usize;
+                let w_offset = ((y_ch * x_channels + x_ch) * w_img_size) as usize;
                 let w = &w[w_offset..w_offset + w_img_size as usize];
                 
-                full_xcorr2d(dx_img, dy_img, w, 1.0, y_rows, y_cols, w_rows, w_cols, s_row, s_col);
+                full_conv2d(dx_img, dy_img, w, N::ONE, y_rows, y_cols, w_rows, w_cols, s_row, s_col);
             }
         }   
     }
 }
 
-pub fn conv2d_grads(dw: &mut [f32], x: &[f32], dy: &[f32], 
+/// Accumulates the gradients of the filters in `y_range`, `dw` holds just those filters
+pub fn conv2d_grads<N: NativeNumber>(dw: &mut [N], x: &[N], dy: &[N], 
                   bs: isize, x_channels: isize, y_channels: isize,
+                  y_range: Range<isize>,
                   x_rows: isize, x_cols: isize,
                   y_rows: isize, y_cols: isize,
+                  w_rows: isize, w_cols: isize,
                   s_row: isize, s_col: isize) {
 
-    let w_cols = x_cols - y_cols + 1;
-    let w_rows = x_rows - y_rows + 1;
-    
     let x_img_size = x_rows * x_cols;
     let dy_img_size = y_rows * y_cols;
     let dw_img_size = w_rows * w_cols;
@@ -227,7 +228,7 @@ pub fn conv2d_grads(dw: &mut [f32], x: &[f32], dy: &[f32],
     let x_batch_size = x_channels * x_img_size;
     let dy_batch_size = y_channels * dy_img_size;
     
-    let dw = &mut dw[0..(y_channels * dw_img_size) as usize];
+    let dw = &mut dw[0..((y_range.end - y_range.start) * x_channels * dw_img_size) as usize];
     let dy = &dy[0..(bs * dy_batch_size) as usize];
     let x = &x[0..(bs * x_batch_size) as usize];
     
@@ -236,19 +237,85 @@ pub fn conv2d_grads(dw: &mut [f32], x: &[f32], dy: &[f32],
             let x_offset = (bi * x_batch_size + x_ch * x_img_size) as usize;
             let x_img = &x[x_offset..x_offset + x_img_size as usize];
             
-            for y_ch in 0..y_channels {
+            for y_ch in y_range.clone() {
                 let dy_offset = (bi * dy_batch_size + y_ch * dy_img_size) as usize;
                 let dy_img = &dy[dy_offset..dy_offset + dy_img_size as usize];
                 
-                let dw_offset = (y_ch * dw_img_size) as usize;
+                let dw_offset = (((y_ch - y_range.start) * x_channels + x_ch) * dw_img_size) as usize;
                 let dw = &mut dw[dw_offset..dw_offset + dw_img_size as usize];
-                
-                valid_conv2d(dw, x_img, dy_img, 1.0, x_rows, x_cols, y_rows, y_cols, s_row, s_col);
+
+                // every filter cell sums the inputs it was multiplied with, weighted by the deltas
+                for w_y in 0..w_rows {
+                    for w_x in 0..w_cols {
+                        let mut xi = w_y * x_cols + w_x;
+                        let mut yi = 0;
+
+                        let mut sum = N::ZERO;
+                        for _ in 0..y_rows {
+                            for y_x in 0..y_cols {
+                                sum += x_img[(xi + s_col * y_x) as usize] * dy_img[(yi + y_x) as usize];
+                            }
+
+                            xi += s_row * x_cols;
+                            yi += y_cols;
+                        }
+
+                        dw[(w_y * w_cols + w_x) as usize] += sum;
+                    }
+                }
             }
         }   
     }
 }
 
+/// Copies `images` of `(x_rows, x_cols)` into the larger `(y_rows, y_cols)` ones at the `(p_row, p_col)` offset
+pub fn pad2d<N: NativeNumber>(y: &mut [N], x: &[N], images: isize,
+             (x_rows, x_cols): (isize, isize),
+             (y_rows, y_cols): (isize, isize),
+             (p_row, p_col): (isize, isize)) {
+
+    let x_img_size = x_rows * x_cols;
+    let y_img_size = y_rows * y_cols;
+
+    let y = &mut y[0..(images * y_img_size) as usize];
+    let x = &x[0..(images * x_img_size) as usize];
+
+    for img in 0..images {
+        for x_y in 0..x_rows {
+            let xi = (img * x_img_size + x_y * x_cols) as usize;
+            let yi = (img * y_img_size + (x_y + p_row) * y_cols + p_col) as usize;
+
+            y[yi..yi + x_cols as usize].copy_from_slice(&x[xi..xi + x_cols as usize]);
+        }
+    }
+}
+
+/// The inverse of `pad2d`, copies the `(y_rows, y_cols)` window at the `(p_row, p_col)` offset out of every image
+pub fn crop2d<N: NativeNumber>(y: &mut [N], x: &[N], images: isize,
+              (x_rows, x_cols): (isize, isize),
+              (y_rows, y_cols): (isize, isize),
+              (p_row, p_col): (isize, isize)) {
+
+    let x_img_size = x_rows * x_cols;
+    let y_img_size = y_rows * y_cols;
+
+    let y = &mut y[0..(images * y_img_size) as usize];
+    let x = &x[0..(images * x_img_size) as usize];
+
+    // `x` may not reach the bottom or right edge of `y`, those cells are left untouched
+    let rows = y_rows.min(x_rows - p_row);
+    let cols = y_cols.min(x_cols - p_col);
+
+    for img in 0..images {
+        for y_y in 0..rows {
+            let xi = (img * x_img_size + (y_y + p_row) * x_cols + p_col) as usize;
+            let yi = (img * y_img_size + y_y * y_cols) as usize;
+
+            y[yi..yi + cols as usize].copy_from_slice(&x[xi..xi + cols as usize]);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -454,7 +521,7 @@ mod test {
                 dy[i] = y[i] - true_y[i];
             }
 
-            conv2d_grads(dw, x, dy, 1, 1, 2, 5, 5, 3, 3, 1, 1);
+            conv2d_grads(dw, x, dy, 1, 1, 2, 0..2, 5, 5, 3, 3, 3, 3, 1, 1);
             
             for i in 0..w.len() {
                 w[i] -= dw[i] * 0.01;
@@ -471,4 +538,80 @@ mod test {
             0.018572278, -0.004654532, 0.011668428
         ]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_conv2d_multichannel() {
+        let (bs, x_channels, y_channels) = (2, 2, 3);
+        let (y_rows, y_cols) = (3, 4);
+
+        let fill = |len: isize, seed: usize| -> Vec<f32> {
+            (0..len as usize).map(|i| ((i * 7 + seed) % 11) as f32 / 11.0 - 0.5).collect()
+        };
+
+        let dot = |a: &[f32], b: &[f32]| -> f32 {
+            a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+        };
+
+        for &(k, s) in &[(2, 1), (3, 1), (3, 2), (5, 1), (5, 2)] {
+            let x_rows = (y_rows - 1) * s + k;
+            let x_cols = (y_cols - 1) * s + k;
+
+            let x = fill(bs * x_channels * x_rows * x_cols, 1);
+            let w = fill(y_channels * x_channels * k * k, 3);
+            let dy = fill(bs * y_channels * y_rows * y_cols, 5);
+
+            let mut y = vec![0.0; (bs * y_channels * y_rows * y_cols) as usize];
+            let mut dx = vec![0.0; x.len()];
+            let mut dw = vec![0.0; w.len()];
+
+            match k {
+                3 => {
+                    conv2d_forward_3x3(&mut y, &x, &w, bs, x_channels, y_channels, x_rows, x_cols, s, s);
+                    conv2d_backward_3x3(&mut dx, &dy, &w, bs, x_channels, y_channels, y_rows, y_cols, s, s);
+                },
+                5 => {
+                    conv2d_forward_5x5(&mut y, &x, &w, bs, x_channels, y_channels, x_rows, x_cols, s, s);
+                    conv2d_backward_5x5(&mut dx, &dy, &w, bs, x_channels, y_channels, y_rows, y_cols, s, s);
+                },
+                _ => {
+                    conv2d_forward(&mut y, &x, &w, bs, x_channels, y_channels, x_rows, x_cols, k, k, s, s);
+                    conv2d_backward(&mut dx, &dy, &w, bs, x_channels, y_channels, y_rows, y_cols, k, k, s, s);
+                },
+            }
+
+            conv2d_grads(&mut dw, &x, &dy, bs, x_channels, y_channels, 0..y_channels, x_rows, x_cols, y_rows, y_cols, k, k, s, s);
+
+            // y[b, o, i, j] = sum over (c, p, q) of x[b, c, s*i + p, s*j + q] * w[o, c, p, q]
+            for b in 0..bs {
+                for o in 0..y_channels {
+                    for i in 0..y_rows {
+                        for j in 0..y_cols {
+                            let mut sum = 0.0;
+
+                            for c in 0..x_channels {
+                                for p in 0..k {
+                                    for q in 0..k {
+                                        let xi = ((b * x_channels + c) * x_rows + s * i + p) * x_cols + s * j + q;
+                                        let wi = ((o * x_channels + c) * k + p) * k + q;
+
+                                        sum += x[xi as usize] * w[wi as usize];
+                                    }
+                                }
+                            }
+
+                            let yi = ((b * y_channels + o) * y_rows + i) * y_cols + j;
+
+                            assert!((y[yi as usize] - sum).abs() < 1e-5);
+                        }
+                    }
+                }
+            }
+
+            // the input and filter gradients are adjoint to the forward pass
+            let expected = dot(&dy, &y);
+
+            assert!((dot(&dx, &x) - expected).abs() < 1e-4);
+            assert!((dot(&dw, &w) - expected).abs() < 1e-4);
+        }
+    }
+}
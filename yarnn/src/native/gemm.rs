@@ -1,105 +1,292 @@
+//! Row-major GEMM: `C = alpha * op(A) * op(B) + beta * C`.
+//!
+//! The product is computed the BLIS way: `op(B)` is cut into `KC x NC` blocks and `op(A)`
+//! into `MC x KC` blocks, both are packed into contiguous panels of `NR` columns and `MR` rows,
+//! and every `MR x NR` tile of `C` is produced by a register blocked micro-kernel.
+//! Transposition only changes the strides the packing routines read with, so all four
+//! variants share the same kernel.
+//!
+//! The micro-kernel is plain Rust over fixed-size arrays, LLVM vectorizes it for whatever
+//! SIMD the target enables (e.g. `-C target-feature=+avx2,+fma`, or `+simd128` on wasm).
 
-fn gemm_nn(m: usize, n: usize, k: usize, alpha: f32, 
-           a: &[f32], lda: usize, 
-           b: &[f32], ldb: usize,
-           c: &mut [f32], ldc: usize)
+use core::cmp::min;
+use core::convert::TryInto;
+use super::NativeNumber;
+
+/// Rows of the micro-kernel tile
+const MR: usize = 4;
+
+/// Columns of the micro-kernel tile
+const NR: usize = 8;
+
+/// Rows of a packed `A` block, sized to keep it in L2 together with a `B` panel
+const MC: usize = 64;
+
+/// Depth of the packed blocks, a `KC x NR` panel of `B` stays in L1
+const KC: usize = 256;
+
+/// Columns of a packed `B` block
+const NC: usize = 1024;
+
+pub fn gemm<N: NativeNumber>(ta: bool, tb: bool, m: usize, n: usize, k: usize, alpha: N,
+        a: &[N], lda: usize,
+        b: &[N], ldb: usize, beta: N,
+        c: &mut [N], ldc: usize)
 {
-    let a = &a[0..m * k];
-    let b = &b[0..n * k];
-    let c = &mut c[0..m * n];
-
-    for i_m in 0..m {
-        for i_k in 0..k {
-            let a_part = alpha * a[i_m * lda + i_k];
-            for i_n in 0..n {
-                c[i_m * ldc + i_n] += a_part * b[i_k * ldb + i_n];
+    for i in 0..m {
+        for j in 0..n {
+            c[i * ldc + j] *= beta;
+        }
+    }
+
+    if m == 0 || n == 0 || k == 0 {
+        return;
+    }
+
+    // strides of op(A)[i, p] and op(B)[p, j] in the underlying slices
+    let (rsa, csa) = if ta { (1, lda) } else { (lda, 1) };
+    let (rsb, csb) = if tb { (1, ldb) } else { (ldb, 1) };
+
+    let kc_max = min(k, KC);
+    let mut packed_a = vec![N::ZERO; round_up(min(m, MC), MR) * kc_max];
+    let mut packed_b = vec![N::ZERO; round_up(min(n, NC), NR) * kc_max];
+
+    for jc in (0..n).step_by(NC) {
+        let nc = min(NC, n - jc);
+
+        for pc in (0..k).step_by(KC) {
+            let kc = min(KC, k - pc);
+
+            pack_b(kc, nc, &b[pc * rsb + jc * csb..], rsb, csb, &mut packed_b);
+
+            for ic in (0..m).step_by(MC) {
+                let mc = min(MC, m - ic);
+
+                pack_a(mc, kc, &a[ic * rsa + pc * csa..], rsa, csa, &mut packed_a);
+
+                for jr in (0..nc).step_by(NR) {
+                    let nr = min(NR, nc - jr);
+                    let b_panel = &packed_b[jr * kc..(jr + NR) * kc];
+
+                    for ir in (0..mc).step_by(MR) {
+                        let mr = min(MR, mc - ir);
+                        let a_panel = &packed_a[ir * kc..(ir + MR) * kc];
+
+                        kernel(alpha, a_panel, b_panel, &mut c[(ic + ir) * ldc + jc + jr..], ldc, mr, nr);
+                    }
+                }
             }
         }
     }
 }
 
-fn gemm_nt(m: usize, n: usize, k: usize, alpha: f32, 
-           a: &[f32], lda: usize, 
-           b: &[f32], ldb: usize,
-           c: &mut [f32], ldc: usize)
-{
-    let a = &a[0..m * k];
-    let b = &b[0..n * k];
-    let c = &mut c[0..m * n];
+#[inline]
+fn round_up(x: usize, to: usize) -> usize {
+    x.div_ceil(to) * to
+}
 
-    for i_m in 0..m {
-        for i_n in 0..n {
-            let mut sum = 0.0;
+/// Packs the `mc x kc` block of `A` into `MR` row panels laid out `p`-major, the rows
+/// past `mc` are zero padded so the kernel always works on full tiles
+fn pack_a<N: NativeNumber>(mc: usize, kc: usize, a: &[N], rsa: usize, csa: usize, packed: &mut [N]) {
+    for (panel_idx, panel) in packed.chunks_exact_mut(MR * kc).take(mc.div_ceil(MR)).enumerate() {
+        let i0 = panel_idx * MR;
+        let rows = min(MR, mc - i0);
 
-            for i_k in 0..k {
-                sum += alpha * a[i_m * lda + i_k] * b[i_n * ldb + i_k];
+        for (p, dst) in panel.chunks_exact_mut(MR).enumerate() {
+            for (ir, val) in dst.iter_mut().enumerate() {
+                *val = if ir < rows { a[(i0 + ir) * rsa + p * csa] } else { N::ZERO };
             }
-
-            c[i_m * ldc + i_n] += sum;
         }
     }
 }
 
-fn gemm_tn(m: usize, n: usize, k: usize, alpha: f32, 
-           a: &[f32], lda: usize, 
-           b: &[f32], ldb: usize,
-           c: &mut [f32], ldc: usize)
-{    
-    let a = &a[0..m * k];
-    let b = &b[0..n * k];
-    let c = &mut c[0..m * n];
-
-    for i_m in 0..m {
-        for i_k in 0..k {
-            let a_part = alpha * a[i_k * lda + i_m];
-
-            for i_n in 0..n {
-                c[i_m * ldc + i_n] += a_part * b[i_k * ldb + i_n];
+/// Packs the `kc x nc` block of `B` into `NR` column panels laid out `p`-major, the columns
+/// past `nc` are zero padded
+fn pack_b<N: NativeNumber>(kc: usize, nc: usize, b: &[N], rsb: usize, csb: usize, packed: &mut [N]) {
+    for (panel_idx, panel) in packed.chunks_exact_mut(NR * kc).take(nc.div_ceil(NR)).enumerate() {
+        let j0 = panel_idx * NR;
+        let cols = min(NR, nc - j0);
+
+        for (p, dst) in panel.chunks_exact_mut(NR).enumerate() {
+            for (jr, val) in dst.iter_mut().enumerate() {
+                *val = if jr < cols { b[p * rsb + (j0 + jr) * csb] } else { N::ZERO };
             }
         }
     }
 }
 
-fn gemm_tt(m: usize, n: usize, k: usize, alpha: f32, 
-           a: &[f32], lda: usize, 
-           b: &[f32], ldb: usize,
-           c: &mut [f32], ldc: usize)
+/// Accumulates `alpha * a_panel * b_panel` into the top left `mr x nr` corner of `c`,
+/// the panels are `MR` and `NR` wide and as deep as the packed block
+#[inline]
+fn kernel<N: NativeNumber>(alpha: N,
+          a: &[N], b: &[N],
+          c: &mut [N], ldc: usize,
+          mr: usize, nr: usize)
 {
-    let a = &a[0..m * k];
-    let b = &b[0..n * k];
-    let c = &mut c[0..m * n];
-    
-    for i_m in 0..m {
-        for i_n in 0..n {
-            let mut sum = 0.0;
-            
-            for i_k in 0..k {
-                sum += alpha * a[i_k * lda + i_m] * b[i_n * ldb + i_k];
+    let kc = a.len() / MR;
+    let mut ab = [[N::ZERO; NR]; MR];
+
+    for (a, b) in a[..kc * MR].chunks_exact(MR).zip(b[..kc * NR].chunks_exact(NR)) {
+        let a: &[N; MR] = a.try_into().unwrap();
+        let b: &[N; NR] = b.try_into().unwrap();
+
+        for (ab_row, &a_val) in ab.iter_mut().zip(a) {
+            for (ab_val, &b_val) in ab_row.iter_mut().zip(b) {
+                *ab_val += a_val * b_val;
             }
+        }
+    }
+
+    for (i, ab_row) in ab.iter().enumerate().take(mr) {
+        let c_row = &mut c[i * ldc..i * ldc + nr];
 
-            c[i_m * ldc + i_n] += sum;
+        for (c_val, &ab_val) in c_row.iter_mut().zip(ab_row) {
+            *c_val += alpha * ab_val;
         }
     }
 }
 
-pub fn gemm(ta: bool, tb: bool, m: usize, n: usize, k: usize, alpha: f32, 
-        a: &[f32], lda: usize, 
-        b: &[f32], ldb: usize, beta: f32,
-        c: &mut [f32], ldc: usize)
-{
-    for i in 0..m {
-        for j in 0..n {
-            c[i * ldc + j] *= beta;
+/// The straightforward loops the blocked version replaced, kept as the reference for tests
+/// and benchmarks. `op(A)` is read through strides, the loop order follows the layout of `B`.
+#[cfg(test)]
+mod naive {
+    pub fn gemm(ta: bool, tb: bool, m: usize, n: usize, k: usize, alpha: f32,
+            a: &[f32], lda: usize,
+            b: &[f32], ldb: usize, beta: f32,
+            c: &mut [f32], ldc: usize)
+    {
+        for i in 0..m {
+            for j in 0..n {
+                c[i * ldc + j] *= beta;
+            }
         }
+
+        let (rsa, csa) = if ta { (1, lda) } else { (lda, 1) };
+
+        for i_m in 0..m {
+            if tb {
+                for i_n in 0..n {
+                    let mut sum = 0.0;
+
+                    for i_k in 0..k {
+                        sum += alpha * a[i_m * rsa + i_k * csa] * b[i_n * ldb + i_k];
+                    }
+
+                    c[i_m * ldc + i_n] += sum;
+                }
+            } else {
+                for i_k in 0..k {
+                    let a_part = alpha * a[i_m * rsa + i_k * csa];
+
+                    for i_n in 0..n {
+                        c[i_m * ldc + i_n] += a_part * b[i_k * ldb + i_n];
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn random_vec(len: usize, seed: u64) -> Vec<f32> {
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    (0..len).map(|_| rng.gen_range(-1.0, 1.0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs both implementations on random `op(A)` of `m x k` and `op(B)` of `k x n`
+    fn check(ta: bool, tb: bool, m: usize, n: usize, k: usize) {
+        let a = random_vec(m * k, 1);
+        let b = random_vec(k * n, 2);
+        let c = random_vec(m * n, 3);
+        let lda = if ta { m } else { k };
+        let ldb = if tb { k } else { n };
+
+        let mut expected = c.clone();
+        let mut actual = c;
+
+        naive::gemm(ta, tb, m, n, k, 0.5, &a, lda, &b, ldb, 0.25, &mut expected, n);
+        gemm(ta, tb, m, n, k, 0.5, &a, lda, &b, ldb, 0.25, &mut actual, n);
+
+        for (i, (x, y)) in expected.iter().zip(actual.iter()).enumerate() {
+            assert!(
+                (x - y).abs() <= 1e-4 * (1.0 + x.abs()),
+                "ta={} tb={} {}x{}x{} [{}]: {} != {}", ta, tb, m, n, k, i, x, y
+            );
+        }
+    }
+
+    #[test]
+    fn test_gemm() {
+        for &(ta, tb) in &[(false, false), (false, true), (true, false), (true, true)] {
+            check(ta, tb, 1, 1, 1);
+            check(ta, tb, 3, 5, 2);
+            check(ta, tb, MR, NR, 7);
+            check(ta, tb, 37, 29, 300);
+            check(ta, tb, MC + 3, 2 * NR + 1, KC + 1);
+            check(ta, tb, 5, NC + 9, 11);
+        }
+    }
+
+    #[test]
+    fn test_gemm_empty() {
+        let mut c = [1.0; 6];
+
+        gemm(false, false, 2, 3, 0, 1.0, &[], 0, &[], 3, 0.5, &mut c, 3);
+        assert_eq!(c, [0.5; 6]);
+
+        gemm(false, false, 0, 3, 4, 1.0, &[], 4, &[0.0; 12], 3, 0.5, &mut c, 3);
+        assert_eq!(c, [0.5; 6]);
     }
+}
+
+#[cfg(test)]
+mod benches {
+    extern crate test;
+
+    use super::*;
+    use self::test::Bencher;
+
+    type Gemm = fn(bool, bool, usize, usize, usize, f32, &[f32], usize, &[f32], usize, f32, &mut [f32], usize);
+
+    fn bench(bencher: &mut Bencher, f: Gemm, ta: bool, tb: bool, size: usize) {
+        let a = random_vec(size * size, 1);
+        let b = random_vec(size * size, 2);
+        let mut c = vec![0.0; size * size];
 
-    if !ta && !tb {
-        gemm_nn(m, n, k, alpha, a, lda, b, ldb, c, ldc);
-    } else if ta && !tb {
-        gemm_tn(m, n, k, alpha, a, lda, b, ldb, c, ldc);
-    } else if !ta && tb {
-        gemm_nt(m, n, k, alpha, a, lda, b, ldb, c, ldc);
-    } else {
-        gemm_tt(m, n, k, alpha, a, lda, b, ldb, c, ldc);
+        bencher.iter(|| {
+            f(ta, tb, size, size, size, 1.0, &a, size, &b, size, 0.0, &mut c, size);
+            test::black_box(&c);
+        });
     }
-}
\ No newline at end of file
+
+    #[bench]
+    fn bench_gemm_nn_64_naive(b: &mut Bencher) { bench(b, naive::gemm, false, false, 64) }
+
+    #[bench]
+    fn bench_gemm_nn_64_blocked(b: &mut Bencher) { bench(b, gemm, false, false, 64) }
+
+    #[bench]
+    fn bench_gemm_nn_256_naive(b: &mut Bencher) { bench(b, naive::gemm, false, false, 256) }
+
+    #[bench]
+    fn bench_gemm_nn_256_blocked(b: &mut Bencher) { bench(b, gemm, false, false, 256) }
+
+    #[bench]
+    fn bench_gemm_nt_256_naive(b: &mut Bencher) { bench(b, naive::gemm, false, true, 256) }
+
+    #[bench]
+    fn bench_gemm_nt_256_blocked(b: &mut Bencher) { bench(b, gemm, false, true, 256) }
+
+    #[bench]
+    fn bench_gemm_tn_256_naive(b: &mut Bencher) { bench(b, naive::gemm, true, false, 256) }
+
+    #[bench]
+    fn bench_gemm_tn_256_blocked(b: &mut Bencher) { bench(b, gemm, true, false, 256) }
+}
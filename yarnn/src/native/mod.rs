@@ -1,6 +1,9 @@
 mod conv2d;
 mod pool2d;
 mod gemm;
+mod batch_norm;
+#[cfg(feature = "parallel")]
+mod parallel;
 
 use crate::tensor::*;
 use crate::backend::*;
@@ -8,13 +11,30 @@ use crate::backend::*;
 use self::conv2d::*;
 use self::pool2d::*;
 use self::gemm::*;
+use self::batch_norm::*;
+#[cfg(feature = "parallel")]
+pub use self::parallel::*;
 
 use core::fmt;
+use core::ops::{Range, Add, Sub, Mul, Div, Neg, AddAssign, SubAssign, MulAssign, DivAssign};
 use core::fmt::Write;
+use rand::Rng;
 use rand_distr::{Normal, Distribution};
 
+/// Lower bound for predicted probabilities in cross entropy, avoids dividing by or taking the log of zero
+const CROSS_ENTROPY_EPSILON: f32 = 1e-7;
+
+
+/// Scalar type of the native backends, the kernels are written against these operations
+pub trait NativeNumber: Copy + Default + PartialOrd + Send + Sync + fmt::Debug + fmt::Display + 'static
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+    + AddAssign + SubAssign + MulAssign + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_INFINITY: Self;
+    const EPSILON: Self;
 
-pub trait NativeNumber: Copy {
     fn from_f32(val: f32) -> Self;
     fn from_f64(val: f64) -> Self;
     fn from_i64(val: i64) -> Self;
@@ -25,34 +45,67 @@ pub trait NativeNumber: Copy {
     fn from_u32(val: u32) -> Self;
     fn from_u16(val: u16) -> Self;
     fn from_u8(val: u8) -> Self;
-}
+    fn from_usize(val: usize) -> Self;
+
+    fn to_f32(self) -> f32;
+    fn to_f64(self) -> f64;
 
-impl NativeNumber for f32 {
-    fn from_f32(val: f32) -> Self { val as f32 }
-    fn from_f64(val: f64) -> Self { val as f32 }
-    fn from_i64(val: i64) -> Self { val as f32 }
-    fn from_i32(val: i32) -> Self { val as f32 }
-    fn from_i16(val: i16) -> Self { val as f32 }
-    fn from_i8(val: i8) -> Self { val as f32 }
-    fn from_u64(val: u64) -> Self { val as f32 }
-    fn from_u32(val: u32) -> Self { val as f32 }
-    fn from_u16(val: u16) -> Self { val as f32 }
-    fn from_u8(val: u8) -> Self { val as f32 }
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn tanh(self) -> Self;
+    fn abs(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+
+    #[inline]
+    fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
 }
 
-impl NativeNumber for f64 {
-    fn from_f32(val: f32) -> Self { val as f64 }
-    fn from_f64(val: f64) -> Self { val as f64 }
-    fn from_i64(val: i64) -> Self { val as f64 }
-    fn from_i32(val: i32) -> Self { val as f64 }
-    fn from_i16(val: i16) -> Self { val as f64 }
-    fn from_i8(val: i8) -> Self { val as f64 }
-    fn from_u64(val: u64) -> Self { val as f64 }
-    fn from_u32(val: u32) -> Self { val as f64 }
-    fn from_u16(val: u16) -> Self { val as f64 }
-    fn from_u8(val: u8) -> Self { val as f64 }
+macro_rules! impl_native_number {
+    ($t:ident) => {
+        impl NativeNumber for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const NEG_INFINITY: Self = $t::NEG_INFINITY;
+            const EPSILON: Self = $t::EPSILON;
+
+            #[inline] fn from_f32(val: f32) -> Self { val as $t }
+            #[inline] fn from_f64(val: f64) -> Self { val as $t }
+            #[inline] fn from_i64(val: i64) -> Self { val as $t }
+            #[inline] fn from_i32(val: i32) -> Self { val as $t }
+            #[inline] fn from_i16(val: i16) -> Self { val as $t }
+            #[inline] fn from_i8(val: i8) -> Self { val as $t }
+            #[inline] fn from_u64(val: u64) -> Self { val as $t }
+            #[inline] fn from_u32(val: u32) -> Self { val as $t }
+            #[inline] fn from_u16(val: u16) -> Self { val as $t }
+            #[inline] fn from_u8(val: u8) -> Self { val as $t }
+            #[inline] fn from_usize(val: usize) -> Self { val as $t }
+
+            #[inline] fn to_f32(self) -> f32 { self as f32 }
+            #[inline] fn to_f64(self) -> f64 { self as f64 }
+
+            #[inline] fn exp(self) -> Self { $t::exp(self) }
+            #[inline] fn ln(self) -> Self { $t::ln(self) }
+            #[inline] fn ln_1p(self) -> Self { $t::ln_1p(self) }
+            #[inline] fn sqrt(self) -> Self { $t::sqrt(self) }
+            #[inline] fn tanh(self) -> Self { $t::tanh(self) }
+            #[inline] fn abs(self) -> Self { $t::abs(self) }
+            #[inline] fn powi(self, n: i32) -> Self { $t::powi(self, n) }
+            #[inline] fn max(self, other: Self) -> Self { $t::max(self, other) }
+            #[inline] fn min(self, other: Self) -> Self { $t::min(self, other) }
+            #[inline] fn clamp(self, min: Self, max: Self) -> Self { $t::clamp(self, min, max) }
+        }
+    };
 }
 
+impl_native_number!(f32);
+impl_native_number!(f64);
+
 pub trait NativeBackend<N: NativeNumber>: Backend<N> + Default {
     fn read_tensor<'a>(&self, t: &'a Self::Tensor) -> &'a [N];
     fn write_tensor<'a>(&self, t: &'a mut Self::Tensor) -> &'a mut [N];
@@ -96,9 +149,65 @@ impl<N: NativeNumber> Tensor<N> for NativeTensor<N> {
 }
 
 #[derive(Default)]
-pub struct Native<N: NativeNumber>(core::marker::PhantomData<N>);
+pub struct Native<N: NativeNumber> {
+    #[cfg(feature = "parallel")]
+    pool: Option<std::sync::Arc<dyn ThreadPool>>,
+    _m: core::marker::PhantomData<N>,
+}
+
+#[cfg(feature = "parallel")]
+impl<N: NativeNumber + Default> Native<N> {
+    /// Backend splitting batched kernels and GEMM row blocks across `pool`,
+    /// the results are identical to the single threaded ones
+    pub fn with_thread_pool(pool: std::sync::Arc<dyn ThreadPool>) -> Self {
+        Self {
+            pool: Some(pool),
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N: NativeNumber> Native<N> {
+    /// Calls `f` over contiguous ranges covering `0 .. items`, each along with its part of `dst`,
+    /// which holds `items` equally sized items. With a thread pool the ranges run as separate jobs,
+    /// otherwise `f` gets the whole range at once. Items must not depend on each other,
+    /// so that the split doesn't change the result.
+    fn for_each_range<F>(&self, items: usize, dst: &mut [N], f: F)
+        where F: Fn(Range<usize>, &mut [N]) + Sync
+    {
+        #[cfg(feature = "parallel")]
+        {
+            if let Some(pool) = &self.pool {
+                let jobs_count = pool.threads().min(items);
+
+                if jobs_count > 1 {
+                    assert_eq!(dst.len() % items, 0);
+
+                    let item_size = dst.len() / items;
+                    let f = &f;
+                    let mut rest = dst;
+                    let mut jobs: Vec<Job> = Vec::with_capacity(jobs_count);
+
+                    for job in 0..jobs_count {
+                        let range = items * job / jobs_count .. items * (job + 1) / jobs_count;
+                        let (chunk, tail) = core::mem::take(&mut rest).split_at_mut(range.len() * item_size);
+
+                        rest = tail;
+                        jobs.push(Box::new(move || f(range, chunk)));
+                    }
 
-impl<N: NativeNumber + core::fmt::Display> Native<N> {
+                    pool.execute(jobs);
+
+                    return;
+                }
+            }
+        }
+
+        f(0 .. items, dst)
+    }
+}
+
+impl<N: NativeNumber> Native<N> {
     fn fmt_tensor(&self, t: &NativeTensor<N>, f: &mut String) -> fmt::Result {
         let strides = t.shape.default_strides();
         let last_idx = strides.dims - 1;
@@ -133,8 +242,8 @@ impl<N: NativeNumber + core::fmt::Display> Native<N> {
     }
 }
 
-impl Backend<f32> for Native<f32> {
-    type Tensor = NativeTensor<f32>;
+impl<N: NativeNumber> Backend<N> for Native<N> {
+    type Tensor = NativeTensor<N>;
 
     fn store_tensor_f32(&self, t: &Self::Tensor, data: &mut [f32]) {
         let size = t.shape().size();
@@ -143,7 +252,7 @@ impl Backend<f32> for Native<f32> {
         let dst = t.read();
 
         for i in 0 .. size {
-            data[i] = dst[i] as f32;
+            data[i] = dst[i].to_f32();
         }
     }
 
@@ -154,7 +263,7 @@ impl Backend<f32> for Native<f32> {
         let dst = &mut t.write()[0..size];
 
         for i in 0 .. size {
-            dst[i] = data[i] as f32;
+            dst[i] = N::from_u8(data[i]);
         }
     }
 
@@ -165,17 +274,27 @@ impl Backend<f32> for Native<f32> {
         let dst = &mut t.write()[0..size];
 
         for i in 0 .. size {
-            dst[i] = data[i];
+            dst[i] = N::from_f32(data[i]);
         }
     }
 
     #[inline]
-    fn scalar_f32(&self, val: f32) -> f32 {
-        val
+    fn scalar_f32(&self, val: f32) -> N {
+        N::from_f32(val)
     }
 
     #[inline]
-    fn fill_scalar(&self, t: &mut Self::Tensor, scalar: f32) {
+    fn scalar_f64(&self, val: f64) -> N {
+        N::from_f64(val)
+    }
+
+    #[inline]
+    fn scalar_to_f64(&self, val: N) -> f64 {
+        val.to_f64()
+    }
+
+    #[inline]
+    fn fill_scalar(&self, t: &mut Self::Tensor, scalar: N) {
         let size = t.shape().size();
         let dst = t.write();
 
@@ -185,17 +304,17 @@ impl Backend<f32> for Native<f32> {
     }
 
     #[inline]
-    fn fill_random(&self, t: &mut Self::Tensor, from: f32, to: f32) {
+    fn fill_random(&self, t: &mut Self::Tensor, from: N, to: N) {
         let seed = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
                     1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
 
         let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed(seed);
-        let normal = Normal::new(from, to).unwrap();
+        let normal = Normal::new(from.to_f32(), to.to_f32()).unwrap();
         let size = t.shape().size();
         let dst = t.write();
 
         for i in 0 .. size {
-            dst[i] = normal.sample(&mut rng);
+            dst[i] = N::from_f32(normal.sample(&mut rng));
         }
     }
 
@@ -206,19 +325,36 @@ impl Backend<f32> for Native<f32> {
     } 
 }
 
-impl NativeBackend<f32> for Native<f32> {
+impl<N: NativeNumber> NativeBackend<N> for Native<N> {
     #[inline]
-    fn read_tensor<'a>(&self, t: &'a Self::Tensor) -> &'a [f32] {
+    fn read_tensor<'a>(&self, t: &'a Self::Tensor) -> &'a [N] {
         t.read()
     }
 
     #[inline]
-    fn write_tensor<'a>(&self, t: &'a mut Self::Tensor) -> &'a mut [f32] {
+    fn write_tensor<'a>(&self, t: &'a mut Self::Tensor) -> &'a mut [N] {
         t.write()
     }
 }
 
-impl BackendGemm<f32> for Native<f32> {
+impl<N: NativeNumber> Native<N> {
+    /// `c = op(a) * op(b)` with `c` of `m x n`, computed by row blocks of `c` on a thread pool,
+    /// all three matrices are densely packed
+    fn gemm_rows(&self, ta: bool, tb: bool, (m, n, k): (usize, usize, usize),
+                 a: &[N], b: &[N], c: &mut [N]) {
+        let lda = if ta { m } else { k };
+        let ldb = if tb { k } else { n };
+
+        // distance between the rows of op(a)
+        let a_row = if ta { 1 } else { lda };
+
+        self.for_each_range(m, &mut c[0 .. m * n], |rows, c| {
+            gemm(ta, tb, rows.len(), n, k, N::ONE, &a[rows.start * a_row ..], lda, b, ldb, N::ZERO, c, n)
+        });
+    }
+}
+
+impl<N: NativeNumber> BackendGemm<N> for Native<N> {
     fn matmul(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
         let a_shape = a.shape();
         let b_shape = b.shape();
@@ -234,15 +370,7 @@ impl BackendGemm<f32> for Native<f32> {
         let n = b_shape.get(1) as usize;
         let k = b_shape.get(0) as usize;
 
-        gemm(
-            false, false,
-            m, n, k, 
-            1.0, 
-            a.read(), k, 
-            b.read(), n, 
-            0.0, 
-            &mut dst.write(), n
-        );
+        self.gemm_rows(false, false, (m, n, k), a.read(), b.read(), dst.write());
     }
 
     fn matmul_nt(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
@@ -260,13 +388,7 @@ impl BackendGemm<f32> for Native<f32> {
         let n = b_shape.get(0) as usize;
         let k = b_shape.get(1) as usize;
 
-        gemm(false, true,
-             m, n, k, 
-             1.0, 
-             a.read(), k, 
-             b.read(), k, 
-             0.0, 
-             &mut dst.write(), n);
+        self.gemm_rows(false, true, (m, n, k), a.read(), b.read(), dst.write());
     }
 
     fn matmul_tn(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
@@ -284,91 +406,61 @@ impl BackendGemm<f32> for Native<f32> {
         let n = b_shape.get(1) as usize;
         let k = b_shape.get(0) as usize;
 
-        gemm(true, false,
-            m, n, k, 
-            1.0, 
-            a.read(), m, 
-            b.read(), n, 
-            0.0, 
-            &mut dst.write(), n);
+        self.gemm_rows(true, false, (m, n, k), a.read(), b.read(), dst.write());
     }
 
-    fn matmul_tt(&self, _dst: &mut Self::Tensor, _a: &Self::Tensor, _b: &Self::Tensor) {
-        unimplemented!();
-    }
-}
-
-impl BackendSigmoid<f32> for Native<f32> {
-    fn sigmoid(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
-        let dst_size = dst.shape().size();
-
-        assert!(dst.shape() == data.shape());
-
-        let data_s = &data.read()[0 .. dst_size];
-        let dst_s = &mut dst.write()[0 .. dst_size];
-
-        for i in 0 .. dst_size {
-            dst_s[i] = 1.0 / (1.0 + (-data_s[i]).exp());
-        }
-    }
+    fn matmul_tt(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
+        let a_shape = a.shape();
+        let b_shape = b.shape();
+        let c_shape = dst.shape().clone();
 
-    fn sigmoid_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
-        let dst_size = dst.shape().size();
+        assert_eq!(a_shape.get(1), c_shape.get(0));
+        assert_eq!(b_shape.get(0), c_shape.get(1));
 
-        assert!(dst.shape() == z.shape());
-        assert!(dst.shape() == d.shape());
+        assert_eq!(a_shape.dims, 2);
+        assert_eq!(b_shape.dims, 2);
 
-        let z_s = &z.read()[0 .. dst_size];
-        let d_s = &d.read()[0 .. dst_size];
-        let dst_s = &mut dst.write()[0 .. dst_size];
+        let m = a_shape.get(1) as usize;
+        let n = b_shape.get(0) as usize;
+        let k = b_shape.get(1) as usize;
 
-        for i in 0 .. dst_size {
-            dst_s[i] = (z_s[i] * (1.0 - z_s[i])) * d_s[i];
-        }
+        self.gemm_rows(true, true, (m, n, k), a.read(), b.read(), dst.write());
     }
 }
 
-impl BackendReLu<f32> for Native<f32> {
-    fn relu(&self, dst: &mut Self::Tensor, data: &Self::Tensor) {
+impl<N: NativeNumber> BackendActivation<N> for Native<N> {
+    fn activation(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
-        assert!(dst.shape() == data.shape());
+        assert!(dst.shape() == x.shape());
 
-        let data_s = &data.read()[0 .. dst_size];
+        let x_s = &x.read()[0 .. dst_size];
         let dst_s = &mut dst.write()[0 .. dst_size];
 
         for i in 0 .. dst_size {
-            let val = if data_s[i] > 0.0 {
-                data_s[i]
-            } else {
-                0.0
-            };
-
-            dst_s[i] = val;
+            dst_s[i] = act.f(x_s[i]);
         }
     }
 
-    fn relu_grad(&self, dst: &mut Self::Tensor, z: &Self::Tensor, d: &Self::Tensor) {
+    fn activation_grad(&self, act: Activation, dst: &mut Self::Tensor, x: &Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
-        assert!(dst.shape() == z.shape());
-        assert!(dst.shape() == d.shape());
+        assert!(dst.shape() == x.shape());
+        assert!(dst.shape() == y.shape());
+        assert!(dst.shape() == dy.shape());
 
-        let z_s = &z.read()[0 .. dst_size];
-        let d_s = &d.read()[0 .. dst_size];
+        let x_s = &x.read()[0 .. dst_size];
+        let y_s = &y.read()[0 .. dst_size];
+        let dy_s = &dy.read()[0 .. dst_size];
         let dst_s = &mut dst.write()[0 .. dst_size];
 
         for i in 0 .. dst_size {
-            dst_s[i] = if z_s[i] > 0.0 {
-                d_s[i]
-            } else {
-                0.0
-            };
+            dst_s[i] = act.df(x_s[i], y_s[i]) * dy_s[i];
         }
     }
 }
 
-impl BackendBias<f32> for Native<f32> {
+impl<N: NativeNumber> BackendBias<N> for Native<N> {
     fn bias_add(&self, dst: &mut Self::Tensor, biases: &Self::Tensor) {
         let biases_shape = biases.shape();
         let dst_shape = dst.shape().clone();
@@ -391,15 +483,17 @@ impl BackendBias<f32> for Native<f32> {
             inner *= *i as usize;
         }
 
-        for b in 0 .. batch_size {
-            for i in 0..inner {
-                for l in 0..biases_size {
-                    let offset = b * (inner * biases_size) + i * biases_size + l;
+        self.for_each_range(batch_size, dst_s, |batches, dst_s| {
+            for b in 0 .. batches.len() {
+                for i in 0..inner {
+                    for l in 0..biases_size {
+                        let offset = b * (inner * biases_size) + i * biases_size + l;
 
-                    dst_s[offset] += biases_s[l];
+                        dst_s[offset] += biases_s[l];
+                    }
                 }
             }
-        }
+        });
     }
     
     fn bias_grad(&self, dbiases: &mut Self::Tensor, deltas: &Self::Tensor) {
@@ -424,22 +518,82 @@ impl BackendBias<f32> for Native<f32> {
             inner *= *i as usize;
         }
 
-        for b in 0 .. batch_size {
-            for l in 0 .. dbiases_size {
-                let mut bias_grad = 0.0;
-                for i in 0 .. inner {
-                    let offset = b * (inner * dbiases_size) + i * dbiases_size + l;
-                    bias_grad += deltas_s[offset];
+        // split over the biases, the sums over the batch keep their order
+        self.for_each_range(dbiases_size, dbiases_s, |biases, dbiases_s| {
+            for (l, dbias) in biases.zip(dbiases_s.iter_mut()) {
+                let mut bias_grad = N::ZERO;
+
+                for b in 0 .. batch_size {
+                    for i in 0 .. inner {
+                        let offset = b * (inner * dbiases_size) + i * dbiases_size + l;
+                        bias_grad += deltas_s[offset];
+                    }
                 }
 
-                dbiases_s[l] = bias_grad;
+                *dbias = bias_grad;
             }
-        }
+        });
+    }
+}
+
+impl<N: NativeNumber> BackendBias2d<N> for Native<N> {
+    fn bias_add2d(&self, dst: &mut Self::Tensor, biases: &Self::Tensor) {
+        let dst_shape = &dst.shape().as_slice()[0..4];
+        let channels = dst_shape[1] as usize;
+
+        assert_eq!(biases.shape().get(0) as usize, channels);
+
+        let batch_size = dst_shape[0] as usize;
+        let img_size = (dst_shape[2] * dst_shape[3]) as usize;
+
+        let biases_s = &biases.read()[0 .. channels];
+        let dst_s = &mut dst.write()[0 .. batch_size * channels * img_size];
+
+        self.for_each_range(batch_size, dst_s, |batches, dst_s| {
+            for b in 0 .. batches.len() {
+                for ch in 0 .. channels {
+                    let offset = (b * channels + ch) * img_size;
+
+                    for i in 0 .. img_size {
+                        dst_s[offset + i] += biases_s[ch];
+                    }
+                }
+            }
+        });
+    }
+
+    fn bias_grad2d(&self, dbiases: &mut Self::Tensor, deltas: &Self::Tensor) {
+        let deltas_shape = &deltas.shape().as_slice()[0..4];
+        let channels = deltas_shape[1] as usize;
+
+        assert_eq!(dbiases.shape().get(0) as usize, channels);
+
+        let batch_size = deltas_shape[0] as usize;
+        let img_size = (deltas_shape[2] * deltas_shape[3]) as usize;
+
+        let dbiases_s = &mut dbiases.write()[0 .. channels];
+        let deltas_s = &deltas.read()[0 .. batch_size * channels * img_size];
+
+        self.for_each_range(channels, dbiases_s, |chs, dbiases_s| {
+            for (ch, dbias) in chs.zip(dbiases_s.iter_mut()) {
+                let mut bias_grad = N::ZERO;
+
+                for b in 0 .. batch_size {
+                    let offset = (b * channels + ch) * img_size;
+
+                    for i in 0 .. img_size {
+                        bias_grad += deltas_s[offset + i];
+                    }
+                }
+
+                *dbias = bias_grad;
+            }
+        });
     }
 }
 
-impl BackendScale<f32> for Native<f32> {
-    fn scale(&self, dst: &mut Self::Tensor, scale: f32) {
+impl<N: NativeNumber> BackendScale<N> for Native<N> {
+    fn scale(&self, dst: &mut Self::Tensor, scale: N) {
         let dst_size = dst.shape().size();
         let dst_s = &mut dst.write()[0 .. dst_size];
 
@@ -449,8 +603,8 @@ impl BackendScale<f32> for Native<f32> {
     }
 }
 
-impl BackendMse<f32> for Native<f32> {
-    fn scaled_square_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: f32) {
+impl<N: NativeNumber> BackendMse<N> for Native<N> {
+    fn scaled_square_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: N) {
         let a_size = a.shape().size();
         let b_size = b.shape().size();
         let dst_size = dst.shape().size();
@@ -469,7 +623,7 @@ impl BackendMse<f32> for Native<f32> {
         }
     }
 
-    fn scaled_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: f32) {
+    fn scaled_diff(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor, scale: N) {
         let a_size = a.shape().size();
         let b_size = b.shape().size();
         let dst_size = dst.shape().size();
@@ -487,8 +641,8 @@ impl BackendMse<f32> for Native<f32> {
     }
 }
 
-impl BackendAxpy<f32> for Native<f32> {
-    default fn axpy(&self, dst: &mut Self::Tensor, scale: f32, a: &Self::Tensor) {
+impl<N: NativeNumber> BackendAxpy<N> for Native<N> {
+    default fn axpy(&self, dst: &mut Self::Tensor, scale: N, a: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
         assert!(a.shape() == dst.shape());
@@ -502,8 +656,8 @@ impl BackendAxpy<f32> for Native<f32> {
     }
 }
 
-impl BackendAxpys<f32> for Native<f32> {
-    fn axpys(&self, dst: &mut Self::Tensor, scale: f32, a: &Self::Tensor) {
+impl<N: NativeNumber> BackendAxpys<N> for Native<N> {
+    fn axpys(&self, dst: &mut Self::Tensor, scale: N, a: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
         assert!(a.shape() == dst.shape());
@@ -517,7 +671,7 @@ impl BackendAxpys<f32> for Native<f32> {
     }
 }
 
-impl BackendAdd<f32> for Native<f32> {
+impl<N: NativeNumber> BackendAdd<N> for Native<N> {
     fn add(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
@@ -532,7 +686,7 @@ impl BackendAdd<f32> for Native<f32> {
     }
 }
 
-impl BackendSub<f32> for Native<f32> {
+impl<N: NativeNumber> BackendSub<N> for Native<N> {
     fn sub(&self, dst: &mut Self::Tensor, a: &Self::Tensor, b: &Self::Tensor) {
         let a_size = a.shape().size();
         let b_size = b.shape().size();
@@ -552,7 +706,7 @@ impl BackendSub<f32> for Native<f32> {
     
 }
 
-impl BackendMul<f32> for Native<f32> {
+impl<N: NativeNumber> BackendMul<N> for Native<N> {
     fn mul(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
@@ -568,7 +722,7 @@ impl BackendMul<f32> for Native<f32> {
 }
 
 
-impl BackendCopy<f32> for Native<f32> {
+impl<N: NativeNumber> BackendCopy<N> for Native<N> {
     fn copy(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         let size = dst.shape().size();
 
@@ -583,7 +737,7 @@ impl BackendCopy<f32> for Native<f32> {
     }
 }
 
-impl BackendMaximum<f32> for Native<f32> {
+impl<N: NativeNumber> BackendMaximum<N> for Native<N> {
     fn maximum(&self, dst: &mut Self::Tensor, a: &Self::Tensor) {
         let dst_size = dst.shape().size();
 
@@ -593,14 +747,14 @@ impl BackendMaximum<f32> for Native<f32> {
         let dst_s = &mut dst.write()[0 .. dst_size];
 
         for i in 0 .. dst_size {
-            dst_s[i] = f32::max(a_s[i], dst_s[i]);
+            dst_s[i] = N::max(a_s[i], dst_s[i]);
         }
     }
 }
 
 
-impl BackendAdam<f32> for Native<f32> {
-    fn adam_p(&self, dst: &mut Self::Tensor, lr: f32, moms: &Self::Tensor, vels: &Self::Tensor, eps: f32) {
+impl<N: NativeNumber> BackendAdam<N> for Native<N> {
+    fn adam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, vels: &Self::Tensor, eps: N) {
         let dst_size = dst.shape().size();
 
         assert!(moms.shape() == dst.shape());
@@ -616,7 +770,67 @@ impl BackendAdam<f32> for Native<f32> {
     }
 }
 
-impl BackendSoftmax<f32> for Native<f32> {
+impl<N: NativeNumber> BackendAdaDelta<N> for Native<N> {
+    fn adadelta_p(&self, dst: &mut Self::Tensor, lr: N, grads: &Self::Tensor, accum: &Self::Tensor, delta_accum: &mut Self::Tensor, rho: N, eps: N) {
+        let dst_size = dst.shape().size();
+
+        assert!(grads.shape() == dst.shape());
+        assert!(accum.shape() == dst.shape());
+        assert!(delta_accum.shape() == dst.shape());
+
+        let grads_s = &grads.read()[0 .. dst_size];
+        let accum_s = &accum.read()[0 .. dst_size];
+        let delta_accum_s = &mut delta_accum.write()[0 .. dst_size];
+        let dst_s = &mut dst.write()[0 .. dst_size];
+
+        for i in 0 .. dst_size {
+            let delta = (delta_accum_s[i] + eps).sqrt() / (accum_s[i] + eps).sqrt() * grads_s[i];
+
+            dst_s[i] += lr * delta;
+            delta_accum_s[i] = rho * delta_accum_s[i] + (N::ONE - rho) * delta * delta;
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendNadam<N> for Native<N> {
+    fn nadam_p(&self, dst: &mut Self::Tensor, lr: N, moms: &Self::Tensor, grads: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, grad_coef: N, eps: N) {
+        let dst_size = dst.shape().size();
+
+        assert!(moms.shape() == dst.shape());
+        assert!(grads.shape() == dst.shape());
+        assert!(vels.shape() == dst.shape());
+
+        let moms_s = &moms.read()[0 .. dst_size];
+        let grads_s = &grads.read()[0 .. dst_size];
+        let vels_s = &vels.read()[0 .. dst_size];
+        let dst_s = &mut dst.write()[0 .. dst_size];
+
+        for i in 0 .. dst_size {
+            dst_s[i] += lr * (mom_coef * moms_s[i] + grad_coef * grads_s[i]) / (vels_s[i].sqrt() + eps);
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendLamb<N> for Native<N> {
+    fn lamb_update(&self, update: &mut Self::Tensor, params: &Self::Tensor, moms: &Self::Tensor, vels: &Self::Tensor, mom_coef: N, vel_coef: N, eps: N, weight_decay: N) {
+        let size = update.shape().size();
+
+        assert!(params.shape() == update.shape());
+        assert!(moms.shape() == update.shape());
+        assert!(vels.shape() == update.shape());
+
+        let params_s = &params.read()[0 .. size];
+        let moms_s = &moms.read()[0 .. size];
+        let vels_s = &vels.read()[0 .. size];
+        let update_s = &mut update.write()[0 .. size];
+
+        for i in 0 .. size {
+            update_s[i] = mom_coef * moms_s[i] / ((vel_coef * vels_s[i]).sqrt() + eps) + weight_decay * params_s[i];
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendSoftmax<N> for Native<N> {
     fn softmax(&self, y: &mut Self::Tensor, x: &Self::Tensor) {
         let y_shape = y.shape();
         let x_shape = x.shape();
@@ -637,7 +851,7 @@ impl BackendSoftmax<f32> for Native<f32> {
             assert!(i + (axis - 1) < size);
 
             // max(x)
-            let mut max_x = core::f32::NEG_INFINITY;
+            let mut max_x = N::NEG_INFINITY;
             for j in 0..axis {
                 let val = x_s[i + j];
                 if val > max_x {
@@ -652,11 +866,11 @@ impl BackendSoftmax<f32> for Native<f32> {
             }
 
             // 1/sum(ex)
-            let mut sum = 0.0;
+            let mut sum = N::ZERO;
             for j in 0..axis {
                 sum += y_s[i + j];
             }
-            let rsum = 1.0 / sum;
+            let rsum = N::ONE / sum;
 
             // ex * (1/sum(ex))
             for j in 0..axis {
@@ -664,235 +878,835 @@ impl BackendSoftmax<f32> for Native<f32> {
             }
         }
     }
-}
 
-impl BackendConv2d<f32> for Native<f32> {
-    type Context = ();
-
-    fn conv2d_forward(&self, y: &mut Self::Tensor, x: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
-        let x_shape = &x.shape().as_slice()[0..4];
-        let y_shape = &y.shape().as_slice()[0..4];
-        let w_shape = &w.shape().as_slice()[0..3];
-
-        assert_eq!(x_shape[0], y_shape[0]);
-
-        let batch_size = x_shape[0] as isize;
-        let y_channels = y_shape[1] as isize;
+    fn softmax_grad(&self, dx: &mut Self::Tensor, y: &Self::Tensor, dy: &Self::Tensor) {
+        let dx_shape = dx.shape();
+        let size = dx_shape.size();
+        let axis = dx_shape.last_axis() as usize;
 
-        let x_channels = x_shape[1] as isize;
-        let x_height = x_shape[2] as isize;
-        let x_width = x_shape[3] as isize;
+        assert!(dx_shape == y.shape());
+        assert!(dx_shape == dy.shape());
 
-        let filter_height = w_shape[1] as isize;
-        let filter_width = w_shape[2] as isize;
-        
-        let (stride_y, stride_x) = conv_info.strides;
-        let _padding = conv_info.padding;
+        let y_s = &y.read()[0 .. size];
+        let dy_s = &dy.read()[0 .. size];
+        let dx_s = &mut dx.write()[0 .. size];
 
-        self.fill_scalar(y, 0.0);
+        for i in (0..size).step_by(axis) {
+            // sum(y * dy)
+            let mut dot = N::ZERO;
+            for j in 0..axis {
+                dot += y_s[i + j] * dy_s[i + j];
+            }
 
-        if filter_height == 3 && filter_width == 3 {
-            conv2d_forward_3x3(
-                y.write(), x.read(), w.read(),
-                batch_size, x_channels, y_channels,
-                x_height, x_width, stride_y as isize, stride_x as isize
-            )
-        } else if filter_height == 5 && filter_width == 5 {
-            conv2d_forward_5x5(
-                y.write(), x.read(), w.read(),
-                batch_size, x_channels, y_channels,
-                x_height, x_width, stride_y as isize, stride_x as isize
-            )
-        } else {
-            conv2d_forward(
-                y.write(), x.read(), w.read(),
-                batch_size, x_channels, y_channels,
-                x_height, x_width, filter_height, filter_width,
-                stride_y as isize, stride_x as isize
-            )
+            // y * (dy - sum(y * dy))
+            for j in 0..axis {
+                let offset = i + j;
+                dx_s[offset] = y_s[offset] * (dy_s[offset] - dot);
+            }
         }
     }
+}
 
-    fn conv2d_backward_input(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
-        let dx_shape = &dx.shape().as_slice()[0..4];
-        let dy_shape = &dy.shape().as_slice()[0..4];
-        let w_shape = &w.shape().as_slice()[0..3];
+impl<N: NativeNumber> BackendCrossEntropy<N> for Native<N> {
+    fn cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
 
-        assert_eq!(dx_shape[0], dy_shape[0]);
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
 
-        let batch_size = dx_shape[0] as isize;
-        let dy_channels = dy_shape[1] as isize;
-        let dy_height = dy_shape[2] as isize;
-        let dy_width = dy_shape[3] as isize;
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
 
-        let dx_channels = dx_shape[1] as isize;
+        for i in 0 .. size {
+            dst_s[i] = -target_s[i] * pred_s[i].max(eps).ln();
+        }
+    }
 
-        let filter_height = w_shape[1] as isize;
-        let filter_width = w_shape[2] as isize;
+    fn cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
 
-        let _padding = conv_info.padding;
-        let (stride_y, stride_x) = conv_info.strides;
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
 
-        self.fill_scalar(dx, 0.0);
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
 
-        if filter_height == 3 && filter_width == 3 {
-            conv2d_backward_3x3(
-                dx.write(), dy.read(), w.read(),
-                batch_size, dx_channels, dy_channels,
-                dy_height, dy_width,
-                stride_y as isize, stride_x as isize
-            )
-        } else if filter_height == 5 && filter_width == 5 {
-            conv2d_backward_5x5(
-                dx.write(), dy.read(), w.read(),
-                batch_size, dx_channels, dy_channels,
-                dy_height, dy_width,
-                stride_y as isize, stride_x as isize
-            )
-        } else {
-            conv2d_backward(
-                dx.write(), dy.read(), w.read(),
-                batch_size, dx_channels, dy_channels,
-                dy_height, dy_width,
-                filter_height, filter_width,
-                stride_y as isize, stride_x as isize
-            )
+        for i in 0 .. size {
+            dst_s[i] = -target_s[i] / pred_s[i].max(eps);
         }
     }
 
-    fn conv2d_backward_filter(&self, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
-        let x_shape = &x.shape().as_slice()[0..4];
-        let dy_shape = &dy.shape().as_slice()[0..4];
 
-        assert_eq!(x_shape[0], dy_shape[0]);
+    fn softmax_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        let dst_shape = dst.shape();
+        let size = dst_shape.size();
+        let axis = dst_shape.last_axis() as usize;
 
-        let batch_size = x_shape[0] as isize;
-        let dy_channels = dy_shape[1] as isize;
-        let dy_height = dy_shape[2] as isize;
-        let dy_width = dy_shape[3] as isize;
+        assert!(dst_shape == logits.shape());
+        assert!(dst_shape == target.shape());
 
-        let x_channels = x_shape[1] as isize;
-        let x_height = x_shape[2] as isize;
-        let x_width = x_shape[3] as isize;
+        let x_s = &logits.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
 
-        let _padding = conv_info.padding;
-        let (stride_y, stride_x) = conv_info.strides;
+        for i in (0..size).step_by(axis) {
+            // max(x)
+            let mut max_x = N::NEG_INFINITY;
+            for j in 0..axis {
+                max_x = max_x.max(x_s[i + j]);
+            }
 
-        self.fill_scalar(dw, 0.0);
+            // log(sum(exp(x - max(x))))
+            let mut sum = N::ZERO;
+            for j in 0..axis {
+                sum += (x_s[i + j] - max_x).exp();
+            }
+            let log_sum = sum.ln();
 
-        conv2d_grads(
-            dw.write(), x.read(), dy.read(), 
-            batch_size, x_channels, dy_channels,
-            x_height, x_width, dy_height, dy_width,
-            stride_y as isize, stride_x as isize
-        )
+            // -t * log_softmax(x)
+            for j in 0..axis {
+                let offset = i + j;
+                dst_s[offset] = -target_s[offset] * (x_s[offset] - max_x - log_sum);
+            }
+        }
     }
 }
 
-impl BackendMaxPool2d<f32> for Native<f32> {
-    fn max_pool2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
-        let x_shape = &x.shape().as_slice()[0..4];
-        let y_shape = &y.shape().as_slice()[0..4];
-
-        assert_eq!(x_shape[0], y_shape[0]);
-        assert_eq!(x_shape[1], y_shape[1]);
-
-        let (stride_y, stride_x) = conv_info.strides;
-        let (stride_y, stride_x) = (stride_y as isize, stride_x as isize);
+impl<N: NativeNumber> BackendBinaryCrossEntropy<N> for Native<N> {
+    fn binary_cross_entropy(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
 
-        let (pool_y, pool_x) = conv_info.kernel;
-        let (pool_y, pool_x) = (pool_y as isize, pool_x as isize);
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
 
-        let batch_size = x_shape[0] as isize;
-        let channels = x_shape[1] as isize;
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
 
-        let x_rows = x_shape[2] as isize;
-        let x_cols = x_shape[3] as isize;
+        for i in 0 .. size {
+            let p = pred_s[i].clamp(eps, N::ONE - eps);
 
-        let y_rows = (x_rows - pool_y) / stride_y + 1;
-        let y_cols = (x_cols - pool_x) / stride_x + 1;
+            dst_s[i] = -(target_s[i] * p.ln() + (N::ONE - target_s[i]) * (N::ONE - p).ln());
+        }
+    }
 
-        assert_eq!(y_rows, y_shape[2] as isize);
-        assert_eq!(y_cols, y_shape[3] as isize);        
+    fn binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
 
-        let x_img_size = x_rows * x_cols;
-        let x_batch_size = x_img_size * channels;
-        
-        let y_img_size = y_rows * y_cols;
-        let y_batch_size = y_img_size * channels;
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
 
-        let x_vals = &x.read()[0..(batch_size * channels * x_img_size) as usize];
-        let y_vals = &mut y.write()[0..(batch_size * channels * y_img_size) as usize];
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
 
-        for bi in 0..batch_size {
-            for ch in 0..channels {
-                let x_offset = (bi * x_batch_size + ch * x_img_size) as usize;
-                let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
-        
-                let y_offset = (bi * y_batch_size + ch * y_img_size) as usize;
-                let y_img = &mut y_vals[y_offset..y_offset + y_img_size as usize];
+        for i in 0 .. size {
+            let p = pred_s[i].clamp(eps, N::ONE - eps);
 
-                maxpool2d(y_img, x_img,  y_rows, y_cols, x_rows, x_cols,
-                          pool_y, pool_x, stride_y, stride_x);
-            }   
+            dst_s[i] = (p - target_s[i]) / (p * (N::ONE - p));
         }
     }
 
-    fn max_pool2d_backprop(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
-        let x_shape = &x.shape().as_slice()[0..4];
-        let dy_shape = &dy.shape().as_slice()[0..4];
-        let dx_shape = &dx.shape().as_slice()[0..4];
+    fn sigmoid_binary_cross_entropy(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
 
-        assert_eq!(x_shape, dx_shape);
-        assert_eq!(x_shape[0], dy_shape[0]);
-        assert_eq!(x_shape[1], dy_shape[1]);
+        assert!(dst.shape() == logits.shape());
+        assert!(dst.shape() == target.shape());
 
-        let batch_size = x_shape[0] as isize;
-        let channels = x_shape[1] as isize;
-        
-        let x_rows = x_shape[2] as isize;
-        let x_cols = x_shape[3] as isize;
+        let x_s = &logits.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
 
-        let (stride_y, stride_x) = conv_info.strides;
-        let (stride_y, stride_x) = (stride_y as isize, stride_x as isize);
+        for i in 0 .. size {
+            let x = x_s[i];
 
-        let (pool_y, pool_x) = conv_info.kernel;
-        let (pool_y, pool_x) = (pool_y as isize, pool_x as isize);
+            // max(x, 0) - x * t + ln(1 + e^-|x|)
+            dst_s[i] = x.max(N::ZERO) - x * target_s[i] + (-x.abs()).exp().ln_1p();
+        }
+    }
 
-        let x_img_size = x_rows * x_cols;
-        let x_batch_size = x_img_size * channels;
-        
-        let y_rows = (x_rows - pool_y) / stride_y + 1;
-        let y_cols = (x_cols - pool_x) / stride_x + 1;
-        
-        let y_img_size = y_rows * y_cols;
-        let y_batch_size = y_img_size * channels;
+    fn sigmoid_binary_cross_entropy_grad(&self, dst: &mut Self::Tensor, logits: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == logits.shape());
+        assert!(dst.shape() == target.shape());
+
+        let x_s = &logits.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in 0 .. size {
+            dst_s[i] = N::ONE / (N::ONE + (-x_s[i]).exp()) - target_s[i];
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendHuber<N> for Native<N> {
+    fn huber(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in 0 .. size {
+            let diff = (pred_s[i] - target_s[i]).abs();
+
+            dst_s[i] = if diff <= delta {
+                N::from_f32(0.5) * diff * diff
+            } else {
+                delta * (diff - N::from_f32(0.5) * delta)
+            };
+        }
+    }
+
+    fn huber_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor, delta: N) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in 0 .. size {
+            dst_s[i] = (pred_s[i] - target_s[i]).clamp(-delta, delta);
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendHinge<N> for Native<N> {
+    fn hinge(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let dst_shape = dst.shape();
+        let size = dst_shape.size();
+        let axis = dst_shape.last_axis() as usize;
+
+        assert!(dst_shape == pred.shape());
+        assert!(dst_shape == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in (0 .. size).step_by(axis) {
+            let label = i + argmax(&target_s[i .. i + axis]);
+
+            for j in i .. i + axis {
+                dst_s[j] = if j == label {
+                    N::ZERO
+                } else {
+                    (N::ONE + pred_s[j] - pred_s[label]).max(N::ZERO)
+                };
+            }
+        }
+    }
+
+    fn hinge_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let dst_shape = dst.shape();
+        let size = dst_shape.size();
+        let axis = dst_shape.last_axis() as usize;
+
+        assert!(dst_shape == pred.shape());
+        assert!(dst_shape == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in (0 .. size).step_by(axis) {
+            let label = i + argmax(&target_s[i .. i + axis]);
+            let mut violations = N::ZERO;
+
+            for j in i .. i + axis {
+                dst_s[j] = if j != label && N::ONE + pred_s[j] - pred_s[label] > N::ZERO {
+                    violations += N::ONE;
+
+                    N::ONE
+                } else {
+                    N::ZERO
+                };
+            }
+
+            dst_s[label] = -violations;
+        }
+    }
+}
+
+/// Index of the first largest value
+fn argmax<N: NativeNumber>(row: &[N]) -> usize {
+    let mut max = 0;
+
+    for (idx, &val) in row.iter().enumerate() {
+        if val > row[max] {
+            max = idx;
+        }
+    }
+
+    max
+}
+
+impl<N: NativeNumber> BackendKlDivergence<N> for Native<N> {
+    fn kl_divergence(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
+
+        for i in 0 .. size {
+            let t = target_s[i];
+
+            // 0 * ln(0) is taken as 0
+            dst_s[i] = if t > N::ZERO {
+                t * (t.ln() - pred_s[i].max(eps).ln())
+            } else {
+                N::ZERO
+            };
+        }
+    }
+
+    fn kl_divergence_grad(&self, dst: &mut Self::Tensor, pred: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == pred.shape());
+        assert!(dst.shape() == target.shape());
+
+        let pred_s = &pred.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+        let eps = N::from_f32(CROSS_ENTROPY_EPSILON);
+
+        for i in 0 .. size {
+            dst_s[i] = -target_s[i] / pred_s[i].max(eps);
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendNll<N> for Native<N> {
+    fn nll(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == log_probs.shape());
+        assert!(dst.shape() == target.shape());
+
+        let log_probs_s = &log_probs.read()[0 .. size];
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in 0 .. size {
+            dst_s[i] = -target_s[i] * log_probs_s[i];
+        }
+    }
+
+    fn nll_grad(&self, dst: &mut Self::Tensor, log_probs: &Self::Tensor, target: &Self::Tensor) {
+        let size = dst.shape().size();
+
+        assert!(dst.shape() == log_probs.shape());
+        assert!(dst.shape() == target.shape());
+
+        let target_s = &target.read()[0 .. size];
+        let dst_s = &mut dst.write()[0 .. size];
+
+        for i in 0 .. size {
+            dst_s[i] = -target_s[i];
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendReduce<N> for Native<N> {
+    fn sum(&self, t: &Self::Tensor) -> N {
+        let size = t.shape().size();
+
+        t.read()[0 .. size].iter().fold(N::ZERO, |acc, &x| acc + x)
+    }
+
+    fn norm_l2(&self, t: &Self::Tensor) -> N {
+        let size = t.shape().size();
+
+        t.read()[0 .. size].iter().fold(N::ZERO, |acc, &x| acc + x * x).sqrt()
+    }
+}
+
+impl<N: NativeNumber> BackendClamp<N> for Native<N> {
+    fn clamp(&self, t: &mut Self::Tensor, min: N, max: N) {
+        let size = t.shape().size();
+
+        assert!(min <= max);
+
+        for x in t.write()[0 .. size].iter_mut() {
+            *x = x.max(min).min(max);
+        }
+    }
+}
+
+impl<N: NativeNumber> BackendDropout<N> for Native<N> {
+    fn dropout(&self, y: &mut Self::Tensor, mask: &mut Self::Tensor, x: &Self::Tensor, rate: N, seed: u64) {
+        let size = y.shape().size();
+
+        assert!(y.shape() == x.shape());
+        assert!(mask.shape() == x.shape());
+        assert!((N::ZERO .. N::ONE).contains(&rate));
+
+        let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(seed);
+        let scale = N::ONE / (N::ONE - rate);
+
+        let x_s = &x.read()[0 .. size];
+        let mask_s = &mut mask.write()[0 .. size];
+        let y_s = &mut y.write()[0 .. size];
+
+        for i in 0 .. size {
+            mask_s[i] = if rng.gen::<f32>() < rate.to_f32() {
+                N::ZERO
+            } else {
+                scale
+            };
+
+            y_s[i] = x_s[i] * mask_s[i];
+        }
+    }
+
+    fn dropout_grad(&self, dx: &mut Self::Tensor, mask: &Self::Tensor, dy: &Self::Tensor) {
+        let size = dx.shape().size();
+
+        assert!(dx.shape() == mask.shape());
+        assert!(dx.shape() == dy.shape());
+
+        let mask_s = &mask.read()[0 .. size];
+        let dy_s = &dy.read()[0 .. size];
+        let dx_s = &mut dx.write()[0 .. size];
+
+        for i in 0 .. size {
+            dx_s[i] = dy_s[i] * mask_s[i];
+        }
+    }
+}
+
+impl<N: NativeNumber> Native<N> {
+    /// Splits a tensor shape into `(batch, features, inner)` for the batch norm kernels
+    fn batch_norm_dims(shape: &TensorShape) -> (usize, usize, usize) {
+        let bs = shape.get(0) as usize;
+        let features = shape.get(1) as usize;
+
+        (bs, features, shape.size() / (bs * features))
+    }
+}
+
+impl<N: NativeNumber> BackendBatchNorm<N> for Native<N> {
+    fn batch_norm_stats(&self, mean: &mut Self::Tensor, var: &mut Self::Tensor, x: &Self::Tensor) {
+        let (bs, features, inner) = Self::batch_norm_dims(x.shape());
+
+        assert_eq!(mean.shape().size(), features);
+        assert_eq!(var.shape().size(), features);
+
+        batch_norm_stats(mean.write(), var.write(), x.read(), bs, features, inner);
+    }
+
+    fn batch_norm(&self, y: &mut Self::Tensor, xhat: &mut Self::Tensor, x: &Self::Tensor,
+                  mean: &Self::Tensor, var: &Self::Tensor, gamma: &Self::Tensor, beta: &Self::Tensor, eps: N) {
+        let (bs, features, inner) = Self::batch_norm_dims(x.shape());
+
+        assert!(y.shape() == x.shape());
+        assert!(xhat.shape() == x.shape());
+        assert_eq!(gamma.shape().size(), features);
+        assert_eq!(beta.shape().size(), features);
+
+        batch_norm(
+            y.write(), xhat.write(), x.read(),
+            mean.read(), var.read(), gamma.read(), beta.read(), eps,
+            bs, features, inner
+        );
+    }
+
+    fn batch_norm_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor,
+                       var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        let (bs, features, inner) = Self::batch_norm_dims(dy.shape());
+
+        assert!(dx.shape() == dy.shape());
+        assert!(xhat.shape() == dy.shape());
+
+        batch_norm_grad(
+            dx.write(), dy.read(), xhat.read(),
+            var.read(), gamma.read(), eps,
+            bs, features, inner
+        );
+    }
+
+    fn batch_norm_inference_grad(&self, dx: &mut Self::Tensor, dy: &Self::Tensor,
+                                 var: &Self::Tensor, gamma: &Self::Tensor, eps: N) {
+        let (bs, features, inner) = Self::batch_norm_dims(dy.shape());
+
+        assert!(dx.shape() == dy.shape());
+        assert_eq!(var.shape().size(), features);
+        assert_eq!(gamma.shape().size(), features);
+
+        batch_norm_inference_grad(dx.write(), dy.read(), var.read(), gamma.read(), eps, bs, features, inner);
+    }
+
+    fn batch_norm_param_grads(&self, dgamma: &mut Self::Tensor, dbeta: &mut Self::Tensor, dy: &Self::Tensor, xhat: &Self::Tensor) {
+        let (bs, features, inner) = Self::batch_norm_dims(dy.shape());
+
+        assert!(xhat.shape() == dy.shape());
+        assert_eq!(dgamma.shape().size(), features);
+        assert_eq!(dbeta.shape().size(), features);
+
+        batch_norm_param_grads(dgamma.write(), dbeta.write(), dy.read(), xhat.read(), bs, features, inner);
+    }
+}
+
+impl<N: NativeNumber> Native<N> {
+    /// Zero pads the images of `x` as requested by `conv_info`, `None` if no padding is needed
+    fn conv2d_padded_input(x: &NativeTensor<N>, conv_info: &Conv2dInfo) -> Option<(Vec<N>, isize, isize)> {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let ((pad_top, pad_bottom), (pad_left, pad_right)) = conv_info.paddings(x_shape[2], x_shape[3]);
+
+        if pad_top + pad_bottom + pad_left + pad_right == 0 {
+            return None;
+        }
+
+        let images = (x_shape[0] * x_shape[1]) as isize;
+        let x_rows = x_shape[2] as isize;
+        let x_cols = x_shape[3] as isize;
+
+        let rows = x_rows + (pad_top + pad_bottom) as isize;
+        let cols = x_cols + (pad_left + pad_right) as isize;
+
+        let mut padded = vec![N::ZERO; (images * rows * cols) as usize];
+
+        pad2d(&mut padded, x.read(), images, (x_rows, x_cols), (rows, cols), (pad_top as isize, pad_left as isize));
+
+        Some((padded, rows, cols))
+    }
+}
+
+impl<N: NativeNumber> BackendConv2d<N> for Native<N> {
+    type Context = ();
+
+    fn conv2d_forward(&self, _ctx: &mut Self::Context, y: &mut Self::Tensor, x: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let y_shape = &y.shape().as_slice()[0..4];
+        let w_shape = &w.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape[0], y_shape[0]);
+        assert_eq!((y_shape[2], y_shape[3]), conv_info.output_size(x_shape[2], x_shape[3]));
+
+        let batch_size = x_shape[0] as isize;
+        let y_channels = y_shape[1] as isize;
+
+        let x_channels = x_shape[1] as isize;
+
+        assert_eq!(w_shape[0] as isize, y_channels);
+        assert_eq!(w_shape[1] as isize, x_channels);
+
+        let filter_height = w_shape[2] as isize;
+        let filter_width = w_shape[3] as isize;
+        
+        let (stride_y, stride_x) = conv_info.strides;
+
+        let padded = Self::conv2d_padded_input(x, conv_info);
+        let (x_vals, x_height, x_width) = match padded {
+            Some((ref vals, rows, cols)) => (&vals[..], rows, cols),
+            None => (x.read(), x_shape[2] as isize, x_shape[3] as isize),
+        };
+
+        self.fill_scalar(y, N::ZERO);
+
+        let x_batch = (x_channels * x_height * x_width) as usize;
+        let w_vals = w.read();
+
+        self.for_each_range(batch_size as usize, y.write(), |batches, y_vals| {
+            let x_vals = &x_vals[batches.start * x_batch .. batches.end * x_batch];
+            let bs = batches.len() as isize;
+
+            if filter_height == 3 && filter_width == 3 {
+                conv2d_forward_3x3(
+                    y_vals, x_vals, w_vals,
+                    bs, x_channels, y_channels,
+                    x_height, x_width, stride_y as isize, stride_x as isize
+                )
+            } else if filter_height == 5 && filter_width == 5 {
+                conv2d_forward_5x5(
+                    y_vals, x_vals, w_vals,
+                    bs, x_channels, y_channels,
+                    x_height, x_width, stride_y as isize, stride_x as isize
+                )
+            } else {
+                conv2d_forward(
+                    y_vals, x_vals, w_vals,
+                    bs, x_channels, y_channels,
+                    x_height, x_width, filter_height, filter_width,
+                    stride_y as isize, stride_x as isize
+                )
+            }
+        });
+    }
+
+    fn conv2d_backward_input(&self, _ctx: &mut Self::Context, dx: &mut Self::Tensor, dy: &Self::Tensor, w: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let dx_shape = &dx.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let w_shape = &w.shape().as_slice()[0..4];
+
+        assert_eq!(dx_shape[0], dy_shape[0]);
+        assert_eq!((dy_shape[2], dy_shape[3]), conv_info.output_size(dx_shape[2], dx_shape[3]));
+
+        let batch_size = dx_shape[0] as isize;
+        let dy_channels = dy_shape[1] as isize;
+        let dy_height = dy_shape[2] as isize;
+        let dy_width = dy_shape[3] as isize;
+
+        let dx_channels = dx_shape[1] as isize;
+        let dx_height = dx_shape[2] as isize;
+        let dx_width = dx_shape[3] as isize;
+
+        assert_eq!(w_shape[0] as isize, dy_channels);
+        assert_eq!(w_shape[1] as isize, dx_channels);
+
+        let filter_height = w_shape[2] as isize;
+        let filter_width = w_shape[3] as isize;
+
+        let ((pad_top, _), (pad_left, _)) = conv_info.paddings(dx_shape[2], dx_shape[3]);
+        let (stride_y, stride_x) = conv_info.strides;
+
+        // the kernels write the part of the padded input covered by the filter,
+        // which is cropped back to `dx` unless it already matches it
+        let rows = (dy_height - 1) * stride_y as isize + filter_height;
+        let cols = (dy_width - 1) * stride_x as isize + filter_width;
+        let direct = rows == dx_height && cols == dx_width;
+
+        let mut covered = if direct {
+            Vec::new()
+        } else {
+            vec![N::ZERO; (batch_size * dx_channels * rows * cols) as usize]
+        };
+
+        self.fill_scalar(dx, N::ZERO);
+
+        {
+            let dx_vals = if direct { dx.write() } else { &mut covered[..] };
+            let dy_batch = (dy_channels * dy_height * dy_width) as usize;
+            let (dy_vals, w_vals) = (dy.read(), w.read());
+
+            self.for_each_range(batch_size as usize, dx_vals, |batches, dx_vals| {
+                let dy_vals = &dy_vals[batches.start * dy_batch .. batches.end * dy_batch];
+                let bs = batches.len() as isize;
+
+                if filter_height == 3 && filter_width == 3 {
+                    conv2d_backward_3x3(
+                        dx_vals, dy_vals, w_vals,
+                        bs, dx_channels, dy_channels,
+                        dy_height, dy_width,
+                        stride_y as isize, stride_x as isize
+                    )
+                } else if filter_height == 5 && filter_width == 5 {
+                    conv2d_backward_5x5(
+                        dx_vals, dy_vals, w_vals,
+                        bs, dx_channels, dy_channels,
+                        dy_height, dy_width,
+                        stride_y as isize, stride_x as isize
+                    )
+                } else {
+                    conv2d_backward(
+                        dx_vals, dy_vals, w_vals,
+                        bs, dx_channels, dy_channels,
+                        dy_height, dy_width,
+                        filter_height, filter_width,
+                        stride_y as isize, stride_x as isize
+                    )
+                }
+            });
+        }
+
+        if !direct {
+            crop2d(dx.write(), &covered, batch_size * dx_channels,
+                   (rows, cols), (dx_height, dx_width), (pad_top as isize, pad_left as isize));
+        }
+    }
+
+    fn conv2d_backward_filter(&self, _ctx: &mut Self::Context, dw: &mut Self::Tensor, x: &Self::Tensor, dy: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let dw_shape = &dw.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape[0], dy_shape[0]);
+        assert_eq!((dy_shape[2], dy_shape[3]), conv_info.output_size(x_shape[2], x_shape[3]));
+
+        let batch_size = x_shape[0] as isize;
+        let dy_channels = dy_shape[1] as isize;
+        let dy_height = dy_shape[2] as isize;
+        let dy_width = dy_shape[3] as isize;
+
+        let x_channels = x_shape[1] as isize;
+
+        assert_eq!(dw_shape[0] as isize, dy_channels);
+        assert_eq!(dw_shape[1] as isize, x_channels);
+
+        let filter_height = dw_shape[2] as isize;
+        let filter_width = dw_shape[3] as isize;
+
+        let (stride_y, stride_x) = conv_info.strides;
+
+        let padded = Self::conv2d_padded_input(x, conv_info);
+        let (x_vals, x_height, x_width) = match padded {
+            Some((ref vals, rows, cols)) => (&vals[..], rows, cols),
+            None => (x.read(), x_shape[2] as isize, x_shape[3] as isize),
+        };
+
+        self.fill_scalar(dw, N::ZERO);
+
+        let dy_vals = dy.read();
+
+        // split over the filters, the sums over the batch keep their order
+        self.for_each_range(dy_channels as usize, dw.write(), |filters, dw_vals| {
+            conv2d_grads(
+                dw_vals, x_vals, dy_vals,
+                batch_size, x_channels, dy_channels,
+                filters.start as isize .. filters.end as isize,
+                x_height, x_width, dy_height, dy_width,
+                filter_height, filter_width,
+                stride_y as isize, stride_x as isize
+            )
+        });
+    }
+}
+
+impl<N: NativeNumber> BackendMaxPool2d<N> for Native<N> {
+    fn max_pool2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let y_shape = &y.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape[0], y_shape[0]);
+        assert_eq!(x_shape[1], y_shape[1]);
+
+        let (stride_y, stride_x) = conv_info.strides;
+        let (stride_y, stride_x) = (stride_y as isize, stride_x as isize);
+
+        let (pool_y, pool_x) = conv_info.kernel;
+        let (pool_y, pool_x) = (pool_y as isize, pool_x as isize);
+
+        let batch_size = x_shape[0] as isize;
+        let channels = x_shape[1] as isize;
+
+        let x_rows = x_shape[2] as isize;
+        let x_cols = x_shape[3] as isize;
+
+        let (y_rows, y_cols) = conv_info.output_size(x_rows as u32, x_cols as u32);
+        let (y_rows, y_cols) = (y_rows as isize, y_cols as isize);
+
+        let ((pad_y, _), (pad_x, _)) = conv_info.paddings(x_rows as u32, x_cols as u32);
+        let (pad_y, pad_x) = (pad_y as isize, pad_x as isize);
+
+        assert_eq!(y_rows, y_shape[2] as isize);
+        assert_eq!(y_cols, y_shape[3] as isize);        
+
+        let x_img_size = x_rows * x_cols;
+        let x_batch_size = x_img_size * channels;
+        
+        let y_img_size = y_rows * y_cols;
+        let y_batch_size = y_img_size * channels;
+
+        let x_vals = &x.read()[0..(batch_size * channels * x_img_size) as usize];
+        let y_vals = &mut y.write()[0..(batch_size * channels * y_img_size) as usize];
+
+        self.for_each_range(batch_size as usize, y_vals, |batches, y_vals| {
+            let start = batches.start as isize;
+
+            for bi in 0..batches.len() as isize {
+                for ch in 0..channels {
+                    let x_offset = ((start + bi) * x_batch_size + ch * x_img_size) as usize;
+                    let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
+        
+                    let y_offset = (bi * y_batch_size + ch * y_img_size) as usize;
+                    let y_img = &mut y_vals[y_offset..y_offset + y_img_size as usize];
+
+                    maxpool2d(y_img, x_img,  y_rows, y_cols, x_rows, x_cols,
+                              pool_y, pool_x, stride_y, stride_x, pad_y, pad_x);
+                }   
+            }
+        });
+    }
+
+    fn max_pool2d_backprop(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let dx_shape = &dx.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape, dx_shape);
+        assert_eq!(x_shape[0], dy_shape[0]);
+        assert_eq!(x_shape[1], dy_shape[1]);
+
+        let batch_size = x_shape[0] as isize;
+        let channels = x_shape[1] as isize;
+        
+        let x_rows = x_shape[2] as isize;
+        let x_cols = x_shape[3] as isize;
+
+        let (stride_y, stride_x) = conv_info.strides;
+        let (stride_y, stride_x) = (stride_y as isize, stride_x as isize);
+
+        let (pool_y, pool_x) = conv_info.kernel;
+        let (pool_y, pool_x) = (pool_y as isize, pool_x as isize);
+
+        let x_img_size = x_rows * x_cols;
+        let x_batch_size = x_img_size * channels;
+        
+        let (y_rows, y_cols) = conv_info.output_size(x_rows as u32, x_cols as u32);
+        let (y_rows, y_cols) = (y_rows as isize, y_cols as isize);
+
+        let ((pad_y, _), (pad_x, _)) = conv_info.paddings(x_rows as u32, x_cols as u32);
+        let (pad_y, pad_x) = (pad_y as isize, pad_x as isize);
+
+        assert_eq!(y_rows, dy_shape[2] as isize);
+        assert_eq!(y_cols, dy_shape[3] as isize);
+        
+        let y_img_size = y_rows * y_cols;
+        let y_batch_size = y_img_size * channels;
 
         let x_size = (batch_size * channels * x_img_size) as usize;
         let y_size = (batch_size * channels * y_img_size) as usize;
 
+        self.fill_scalar(dx, N::ZERO);
+
         let x_vals = &x.read()[0..x_size];
         let dy_vals = &dy.read()[0..y_size];
         let dx_vals = &mut dx.write()[0..x_size];
 
-        for bi in 0..batch_size {
-            for ch in 0..channels {
-                let x_offset = (bi * x_batch_size + ch * x_img_size) as usize;
-                let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
-                let dx_img = &mut dx_vals[x_offset..x_offset + x_img_size as usize];
+        self.for_each_range(batch_size as usize, dx_vals, |batches, dx_vals| {
+            let start = batches.start as isize;
+
+            for bi in 0..batches.len() as isize {
+                for ch in 0..channels {
+                    let x_offset = ((start + bi) * x_batch_size + ch * x_img_size) as usize;
+                    let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
+
+                    let dx_offset = (bi * x_batch_size + ch * x_img_size) as usize;
+                    let dx_img = &mut dx_vals[dx_offset..dx_offset + x_img_size as usize];
         
-                let dy_offset = (bi * y_batch_size + ch * y_img_size) as usize;
-                let dy_img = &dy_vals[dy_offset..dy_offset + y_img_size as usize];
+                    let dy_offset = ((start + bi) * y_batch_size + ch * y_img_size) as usize;
+                    let dy_img = &dy_vals[dy_offset..dy_offset + y_img_size as usize];
 
-                maxpool2d_backward(dx_img, x_img, dy_img,
-                                   x_rows, x_cols, y_rows, y_cols,
-                                   pool_y, pool_x, stride_y, stride_x);
-            }   
-        }
+                    maxpool2d_backward(dx_img, x_img, dy_img,
+                                       x_rows, x_cols, y_rows, y_cols,
+                                       pool_y, pool_x, stride_y, stride_x, pad_y, pad_x);
+                }   
+            }
+        });
     }
 }
 
-impl BackendAvgPool2d<f32> for Native<f32> {
+impl<N: NativeNumber> BackendAvgPool2d<N> for Native<N> {
     fn avg_pool2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
         let x_shape = &x.shape().as_slice()[0..4];
         let y_shape = &y.shape().as_slice()[0..4];
@@ -912,8 +1726,11 @@ impl BackendAvgPool2d<f32> for Native<f32> {
         let x_rows = x_shape[2] as isize;
         let x_cols = x_shape[3] as isize;
 
-        let y_rows = (x_rows - pool_y) / stride_y + 1;
-        let y_cols = (x_cols - pool_x) / stride_x + 1;
+        let (y_rows, y_cols) = conv_info.output_size(x_rows as u32, x_cols as u32);
+        let (y_rows, y_cols) = (y_rows as isize, y_cols as isize);
+
+        let ((pad_y, _), (pad_x, _)) = conv_info.paddings(x_rows as u32, x_cols as u32);
+        let (pad_y, pad_x) = (pad_y as isize, pad_x as isize);
 
         assert_eq!(y_rows, y_shape[2] as isize);
         assert_eq!(y_cols, y_shape[3] as isize);        
@@ -927,26 +1744,89 @@ impl BackendAvgPool2d<f32> for Native<f32> {
         let x_vals = &x.read()[0..(batch_size * channels * x_img_size) as usize];
         let y_vals = &mut y.write()[0..(batch_size * channels * y_img_size) as usize];
 
-        for bi in 0..batch_size {
-            for ch in 0..channels {
-                let x_offset = (bi * x_batch_size + ch * x_img_size) as usize;
-                let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
+        self.for_each_range(batch_size as usize, y_vals, |batches, y_vals| {
+            let start = batches.start as isize;
+
+            for bi in 0..batches.len() as isize {
+                for ch in 0..channels {
+                    let x_offset = ((start + bi) * x_batch_size + ch * x_img_size) as usize;
+                    let x_img = &x_vals[x_offset..x_offset + x_img_size as usize];
         
-                let y_offset = (bi * y_batch_size + ch * y_img_size) as usize;
-                let y_img = &mut y_vals[y_offset..y_offset + y_img_size as usize];
+                    let y_offset = (bi * y_batch_size + ch * y_img_size) as usize;
+                    let y_img = &mut y_vals[y_offset..y_offset + y_img_size as usize];
 
-                avgpool2d(y_img, x_img,  y_rows, y_cols, x_rows, x_cols,
-                          pool_y, pool_x, stride_y, stride_x);
-            }   
-        }
+                    avgpool2d(y_img, x_img,  y_rows, y_cols, x_rows, x_cols,
+                              pool_y, pool_x, stride_y, stride_x, pad_y, pad_x);
+                }   
+            }
+        });
     }
 
-    fn avg_pool2d_backprop(&self, _dx: &mut Self::Tensor, _dy: &Self::Tensor, _x: &Self::Tensor, _conv_info: &Conv2dInfo) {
-        unimplemented!()
+    fn avg_pool2d_backprop(&self, dx: &mut Self::Tensor, dy: &Self::Tensor, x: &Self::Tensor, conv_info: &Conv2dInfo) {
+        let x_shape = &x.shape().as_slice()[0..4];
+        let dy_shape = &dy.shape().as_slice()[0..4];
+        let dx_shape = &dx.shape().as_slice()[0..4];
+
+        assert_eq!(x_shape, dx_shape);
+        assert_eq!(x_shape[0], dy_shape[0]);
+        assert_eq!(x_shape[1], dy_shape[1]);
+
+        let batch_size = x_shape[0] as isize;
+        let channels = x_shape[1] as isize;
+        
+        let x_rows = x_shape[2] as isize;
+        let x_cols = x_shape[3] as isize;
+
+        let (stride_y, stride_x) = conv_info.strides;
+        let (stride_y, stride_x) = (stride_y as isize, stride_x as isize);
+
+        let (pool_y, pool_x) = conv_info.kernel;
+        let (pool_y, pool_x) = (pool_y as isize, pool_x as isize);
+
+        let x_img_size = x_rows * x_cols;
+        let x_batch_size = x_img_size * channels;
+        
+        let (y_rows, y_cols) = conv_info.output_size(x_rows as u32, x_cols as u32);
+        let (y_rows, y_cols) = (y_rows as isize, y_cols as isize);
+
+        let ((pad_y, _), (pad_x, _)) = conv_info.paddings(x_rows as u32, x_cols as u32);
+        let (pad_y, pad_x) = (pad_y as isize, pad_x as isize);
+
+        assert_eq!(y_rows, dy_shape[2] as isize);
+        assert_eq!(y_cols, dy_shape[3] as isize);
+        
+        let y_img_size = y_rows * y_cols;
+        let y_batch_size = y_img_size * channels;
+
+        let x_size = (batch_size * channels * x_img_size) as usize;
+        let y_size = (batch_size * channels * y_img_size) as usize;
+
+        self.fill_scalar(dx, N::ZERO);
+
+        let dy_vals = &dy.read()[0..y_size];
+        let dx_vals = &mut dx.write()[0..x_size];
+
+        self.for_each_range(batch_size as usize, dx_vals, |batches, dx_vals| {
+            let start = batches.start as isize;
+
+            for bi in 0..batches.len() as isize {
+                for ch in 0..channels {
+                    let dx_offset = (bi * x_batch_size + ch * x_img_size) as usize;
+                    let dx_img = &mut dx_vals[dx_offset..dx_offset + x_img_size as usize];
+        
+                    let dy_offset = ((start + bi) * y_batch_size + ch * y_img_size) as usize;
+                    let dy_img = &dy_vals[dy_offset..dy_offset + y_img_size as usize];
+
+                    avgpool2d_backward(dx_img, dy_img,
+                                       x_rows, x_cols, y_rows, y_cols,
+                                       pool_y, pool_x, stride_y, stride_x, pad_y, pad_x);
+                }   
+            }
+        });
     }
 }
 
-impl BackendPaddingCopy2d<f32> for Native<f32> {
+impl<N: NativeNumber> BackendPaddingCopy2d<N> for Native<N> {
     fn copy_with_padding2d(&self, y: &mut Self::Tensor, x: &Self::Tensor, y_paddings: (u32, u32), x_paddings: (u32, u32)) {
         let y_shape = &y.shape().as_slice()[0..4];
         let x_shape = &x.shape().as_slice()[0..4];
@@ -1059,6 +1939,213 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conv2d_info() {
+        let info = |padding, kernel, strides| Conv2dInfo { padding, kernel, strides };
+
+        let same = info(PaddingKind::Same, (3, 3), (1, 1));
+        assert_eq!(same.paddings(5, 5), ((1, 1), (1, 1)));
+        assert_eq!(same.output_size(5, 5), (5, 5));
+
+        let same = info(PaddingKind::Same, (2, 2), (2, 2));
+        assert_eq!(same.paddings(3, 4), ((0, 1), (0, 0)));
+        assert_eq!(same.output_size(3, 4), (2, 2));
+
+        let full = info(PaddingKind::Full, (2, 3), (1, 1));
+        assert_eq!(full.paddings(3, 3), ((1, 1), (2, 2)));
+        assert_eq!(full.output_size(3, 3), (4, 5));
+
+        let valid = info(PaddingKind::Valid, (3, 3), (2, 2));
+        assert_eq!(valid.paddings(8, 8), ((0, 0), (0, 0)));
+        assert_eq!(valid.output_size(8, 8), (3, 3));
+    }
+
+    #[test]
+    fn test_conv2d_same() {
+        let bac: Native<f32> = Default::default();
+        let conv_info = Conv2dInfo { padding: PaddingKind::Same, kernel: (3, 3), strides: (1, 1) };
+
+        let mut x = NativeTensor::new((1, 1, 3, 3));
+        let mut w = NativeTensor::new((1, 1, 3, 3));
+        let mut y = NativeTensor::new((1, 1, 3, 3));
+        let mut dy = NativeTensor::new((1, 1, 3, 3));
+        let mut dx = NativeTensor::new((1, 1, 3, 3));
+        let mut dw = NativeTensor::new((1, 1, 3, 3));
+
+        bac.load_tensor_u8(&mut x, &[
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+        ]);
+
+        bac.fill_scalar(&mut w, 1.0);
+        bac.fill_scalar(&mut dy, 1.0);
+
+        bac.conv2d_forward(&mut (), &mut y, &x, &w, &conv_info);
+        bac.conv2d_backward_input(&mut (), &mut dx, &dy, &w, &conv_info);
+        bac.conv2d_backward_filter(&mut (), &mut dw, &x, &dy, &conv_info);
+
+        assert_eq!(y.read(), &[
+            12.0, 21.0, 16.0,
+            27.0, 45.0, 33.0,
+            24.0, 39.0, 28.0,
+        ]);
+
+        assert_eq!(dx.read(), &[
+            4.0, 6.0, 4.0,
+            6.0, 9.0, 6.0,
+            4.0, 6.0, 4.0,
+        ]);
+
+        assert_eq!(dw.read(), &[
+            12.0, 21.0, 16.0,
+            27.0, 45.0, 33.0,
+            24.0, 39.0, 28.0,
+        ]);
+    }
+
+    #[test]
+    fn test_conv2d_full() {
+        let bac: Native<f32> = Default::default();
+        let conv_info = Conv2dInfo { padding: PaddingKind::Full, kernel: (2, 2), strides: (1, 1) };
+
+        let mut x = NativeTensor::new((1, 1, 2, 2));
+        let mut w = NativeTensor::new((1, 1, 2, 2));
+        let mut y = NativeTensor::new((1, 1, 3, 3));
+        let mut dy = NativeTensor::new((1, 1, 3, 3));
+        let mut dx = NativeTensor::new((1, 1, 2, 2));
+        let mut dw = NativeTensor::new((1, 1, 2, 2));
+
+        bac.load_tensor_u8(&mut x, &[
+            1, 2,
+            3, 4,
+        ]);
+
+        bac.load_tensor_u8(&mut w, &[
+            1, 0,
+            0, 1,
+        ]);
+
+        bac.fill_scalar(&mut dy, 1.0);
+
+        bac.conv2d_forward(&mut (), &mut y, &x, &w, &conv_info);
+        bac.conv2d_backward_input(&mut (), &mut dx, &dy, &w, &conv_info);
+        bac.conv2d_backward_filter(&mut (), &mut dw, &x, &dy, &conv_info);
+
+        assert_eq!(y.read(), &[
+            1.0, 2.0, 0.0,
+            3.0, 5.0, 2.0,
+            0.0, 3.0, 4.0,
+        ]);
+
+        assert_eq!(dx.read(), &[
+            2.0, 2.0,
+            2.0, 2.0,
+        ]);
+
+        // every filter tap sees each input exactly once
+        assert_eq!(dw.read(), &[
+            10.0, 10.0,
+            10.0, 10.0,
+        ]);
+    }
+
+    #[test]
+    fn test_conv2d_strided_remainder() {
+        let bac: Native<f32> = Default::default();
+        let conv_info = Conv2dInfo { padding: PaddingKind::Valid, kernel: (3, 3), strides: (2, 2) };
+
+        let mut w = NativeTensor::new((1, 1, 3, 3));
+        let mut dy = NativeTensor::new((1, 1, 1, 1));
+        let mut dx = NativeTensor::new((1, 1, 4, 4));
+
+        bac.fill_scalar(&mut w, 1.0);
+        bac.fill_scalar(&mut dy, 1.0);
+
+        bac.conv2d_backward_input(&mut (), &mut dx, &dy, &w, &conv_info);
+
+        assert_eq!(dx.read(), &[
+            1.0, 1.0, 1.0, 0.0,
+            1.0, 1.0, 1.0, 0.0,
+            1.0, 1.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+    }
+
+    #[test]
+    fn test_pool2d_same() {
+        let bac: Native<f32> = Default::default();
+        let conv_info = Conv2dInfo { padding: PaddingKind::Same, kernel: (2, 2), strides: (2, 2) };
+
+        let mut x = NativeTensor::new((1, 1, 3, 3));
+        let mut y = NativeTensor::new((1, 1, 2, 2));
+        let mut dy = NativeTensor::new((1, 1, 2, 2));
+        let mut dx = NativeTensor::new((1, 1, 3, 3));
+
+        bac.load_tensor_u8(&mut x, &[
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+        ]);
+
+        bac.load_tensor_u8(&mut dy, &[
+            1, 2,
+            3, 4,
+        ]);
+
+        bac.max_pool2d(&mut y, &x, &conv_info);
+
+        assert_eq!(y.read(), &[
+            5.0, 6.0,
+            8.0, 9.0,
+        ]);
+
+        bac.max_pool2d_backprop(&mut dx, &dy, &x, &conv_info);
+
+        assert_eq!(dx.read(), &[
+            0.0, 0.0, 0.0,
+            0.0, 1.0, 2.0,
+            0.0, 3.0, 4.0,
+        ]);
+
+        bac.avg_pool2d(&mut y, &x, &conv_info);
+
+        assert_eq!(y.read(), &[
+            3.0, 4.5,
+            7.5, 9.0,
+        ]);
+    }
+
+    #[test]
+    fn test_bias2d() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((2, 2, 1, 2));
+        let mut b = NativeTensor::new((2, ));
+        let mut db = NativeTensor::new((2, ));
+
+        bac.load_tensor_u8(&mut x, &[
+            1, 2,
+            3, 4,
+
+            5, 6,
+            7, 8,
+        ]);
+
+        bac.load_tensor_u8(&mut b, &[1, 2]);
+
+        bac.bias_grad2d(&mut db, &x);
+        bac.bias_add2d(&mut x, &b);
+
+        assert_eq!(db.read(), &[14.0, 22.0]);
+        assert_eq!(x.read(), &[
+            2.0, 3.0,
+            5.0, 6.0,
+
+            6.0, 7.0,
+            9.0, 10.0,
+        ]);
+    }
+
     #[test]
     fn test_softmax() {
         let bac: Native<f32> = Default::default();
@@ -1082,6 +2169,133 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_softmax_grad() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((2, 3));
+        let mut y = NativeTensor::new((2, 3));
+        let mut dy = NativeTensor::new((2, 3));
+        let mut dx = NativeTensor::new((2, 3));
+
+        let x_vals: &[f32] = &[0.5, -1.0, 2.0, 0.1, 0.2, -0.3];
+        let dy_vals: &[f32] = &[0.3, -0.7, 1.1, -0.2, 0.9, 0.4];
+
+        bac.load_tensor_f32(&mut x, x_vals);
+        bac.load_tensor_f32(&mut dy, dy_vals);
+
+        bac.softmax(&mut y, &x);
+        bac.softmax_grad(&mut dx, &y, &dy);
+
+        // L = sum(dy * softmax(x))
+        let objective = |vals: &[f32]| -> f32 {
+            let mut x = NativeTensor::new((2, 3));
+            let mut y = NativeTensor::new((2, 3));
+
+            bac.load_tensor_f32(&mut x, vals);
+            bac.softmax(&mut y, &x);
+
+            y.read().iter().zip(dy_vals.iter()).map(|(a, b)| a * b).sum()
+        };
+
+        let h = 1e-2;
+
+        for i in 0..6 {
+            let mut xp = x_vals.to_vec();
+            let mut xm = x_vals.to_vec();
+
+            xp[i] += h;
+            xm[i] -= h;
+
+            let numeric = (objective(&xp) - objective(&xm)) / (2.0 * h);
+
+            assert!((numeric - dx.read()[i]).abs() < 1e-3, "dx[{}]: {} != {}", i, dx.read()[i], numeric);
+        }
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_grad() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((2, 3));
+        let mut y = NativeTensor::new((2, 3));
+        let mut t = NativeTensor::new((2, 3));
+        let mut dy = NativeTensor::new((2, 3));
+        let mut dx = NativeTensor::new((2, 3));
+
+        bac.load_tensor_f32(&mut x, &[0.5, -1.0, 2.0, 0.1, 0.2, -0.3]);
+        bac.load_tensor_u8(&mut t, &[0, 0, 1, 1, 0, 0]);
+
+        bac.softmax(&mut y, &x);
+        bac.cross_entropy_grad(&mut dy, &y, &t);
+        bac.softmax_grad(&mut dx, &y, &dy);
+
+        // chained through softmax the cross entropy gradient is `y - t`
+        for i in 0..6 {
+            assert!((dx.read()[i] - (y.read()[i] - t.read()[i])).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_cross_entropy() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((2, 3));
+        let mut y = NativeTensor::new((2, 3));
+        let mut t = NativeTensor::new((2, 3));
+        let mut loss = NativeTensor::new((2, 3));
+        let mut fused = NativeTensor::new((2, 3));
+
+        bac.load_tensor_f32(&mut y, &[0.2, 0.3, 0.5, 0.0, 0.9, 0.1]);
+        bac.load_tensor_u8(&mut t, &[0, 0, 1, 1, 0, 0]);
+
+        bac.cross_entropy(&mut loss, &y, &t);
+
+        // log(0) is clamped
+        let expected = -(0.5f32.ln() + 1e-7f32.ln());
+        assert!((bac.sum(&loss) - expected).abs() < 1e-4);
+
+        bac.load_tensor_f32(&mut x, &[0.5, -1.0, 2.0, 100.0, -50.0, 20.0]);
+        bac.softmax(&mut y, &x);
+        bac.cross_entropy(&mut loss, &y, &t);
+        bac.softmax_cross_entropy(&mut fused, &x, &t);
+
+        for i in 0..6 {
+            assert!((loss.read()[i] - fused.read()[i]).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_dropout() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((4, 64));
+        let mut y = NativeTensor::new((4, 64));
+        let mut mask = NativeTensor::new((4, 64));
+        let mut dy = NativeTensor::new((4, 64));
+        let mut dx = NativeTensor::new((4, 64));
+
+        bac.fill_scalar(&mut x, 1.0);
+        bac.fill_scalar(&mut dy, 3.0);
+
+        bac.dropout(&mut y, &mut mask, &x, 0.25, 42);
+        bac.dropout_grad(&mut dx, &mask, &dy);
+
+        let dropped = y.read().iter().filter(|&&v| v == 0.0).count();
+
+        assert!(dropped > 32 && dropped < 96);
+
+        for i in 0 .. 256 {
+            assert!(mask.read()[i] == 0.0 || mask.read()[i] == 1.0 / 0.75);
+            assert_eq!(y.read()[i], mask.read()[i]);
+            assert_eq!(dx.read()[i], 3.0 * mask.read()[i]);
+        }
+
+        let mut y2 = NativeTensor::new((4, 64));
+
+        bac.dropout(&mut y2, &mut mask, &x, 0.25, 42);
+        assert!(y.read() == y2.read());
+
+        bac.dropout(&mut y2, &mut mask, &x, 0.25, 43);
+        assert!(y.read() != y2.read());
+    }
+
     #[test]
     fn test_matmul() {
         let bac: Native<f32> = Default::default();
@@ -1182,6 +2396,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matmul_tt() {
+        let bac: Native<f32> = Default::default();
+        let mut a = NativeTensor::new((3, 2));
+        let mut b = NativeTensor::new((4, 3));
+        let mut c = NativeTensor::new((2, 4));
+
+        bac.load_tensor_u8(&mut a, &[
+            1,4,
+            2,5,
+            3,6
+        ]);
+
+        bac.load_tensor_u8(&mut b, &[
+            1,5,9,
+            2,6,10,
+            3,7,11,
+            4,8,12
+        ]);
+
+        bac.matmul_tt(&mut c, &a, &b);
+
+        assert!(
+            c.read() == [
+                38.0,  44.0,  50.0,  56.0,
+                83.0,  98.0, 113.0, 128.0,
+            ]
+        );
+    }
+
 
     #[test]
     fn test_axpy() {
@@ -1215,5 +2459,191 @@ mod tests {
         assert!(
             a.read() == &[2.0, 4.0, 6.0, 8.0]
         );
-    } 
-}
\ No newline at end of file
+    }
+
+    #[test]
+    fn test_activations_grad() {
+        let bac: Native<f32> = Default::default();
+        let x_vals: &[f32] = &[-2.5, -0.7, -0.1, 0.2, 0.9, 3.0];
+        let dy_vals: &[f32] = &[0.3, -0.7, 1.1, -0.2, 0.9, 0.4];
+
+        let activations = [
+            Activation::ReLu,
+            Activation::LeakyReLu { slope: 0.1 },
+            Activation::Elu { alpha: 1.5 },
+            Activation::Sigmoid,
+            Activation::Tanh,
+            Activation::Gelu,
+            Activation::Swish,
+            Activation::Softplus,
+        ];
+
+        for &act in activations.iter() {
+            let mut x = NativeTensor::new((2, 3));
+            let mut y = NativeTensor::new((2, 3));
+            let mut dy = NativeTensor::new((2, 3));
+            let mut dx = NativeTensor::new((2, 3));
+
+            bac.load_tensor_f32(&mut x, x_vals);
+            bac.load_tensor_f32(&mut dy, dy_vals);
+
+            bac.activation(act, &mut y, &x);
+            bac.activation_grad(act, &mut dx, &x, &y, &dy);
+
+            // L = sum(dy * act(x))
+            let objective = |vals: &[f32]| -> f32 {
+                let mut x = NativeTensor::new((2, 3));
+                let mut y = NativeTensor::new((2, 3));
+
+                bac.load_tensor_f32(&mut x, vals);
+                bac.activation(act, &mut y, &x);
+
+                y.read().iter().zip(dy_vals.iter()).map(|(a, b)| a * b).sum()
+            };
+
+            let h = 1e-2;
+
+            for i in 0..6 {
+                let mut xp = x_vals.to_vec();
+                let mut xm = x_vals.to_vec();
+
+                xp[i] += h;
+                xm[i] -= h;
+
+                let numeric = (objective(&xp) - objective(&xm)) / (2.0 * h);
+
+                assert!((numeric - dx.read()[i]).abs() < 1e-3, "{:?} dx[{}]: {} != {}", act, i, dx.read()[i], numeric);
+            }
+        }
+    }
+
+    #[test]
+    fn test_activations() {
+        let bac: Native<f32> = Default::default();
+        let mut x = NativeTensor::new((4, ));
+        let mut y = NativeTensor::new((4, ));
+
+        bac.load_tensor_f32(&mut x, &[-100.0, -1.0, 0.5, 100.0]);
+
+        bac.activation(Activation::Softplus, &mut y, &x);
+        assert!(y.read()[0] < 1e-30);
+        assert!((y.read()[2] - (1.0 + 0.5f32.exp()).ln()).abs() < 1e-6);
+        assert_eq!(y.read()[3], 100.0);
+
+        bac.activation(Activation::Gelu, &mut y, &x);
+        assert_eq!(y.read()[3], 100.0);
+        assert!((y.read()[1] + 0.158_808).abs() < 1e-4);
+
+        bac.activation(Activation::Elu { alpha: 2.0 }, &mut y, &x);
+        assert!((y.read()[0] + 2.0).abs() < 1e-6);
+        assert_eq!(y.read()[2], 0.5);
+    }
+
+    #[test]
+    fn test_f64() {
+        let bac32: Native<f32> = Default::default();
+        let bac: Native<f64> = Default::default();
+
+        // same seed and samples, so both backends start from equal values
+        let mut a32 = NativeTensor::new((7, 5));
+        let mut b32 = NativeTensor::new((5, 6));
+        let mut c32 = NativeTensor::new((7, 6));
+        let mut a = NativeTensor::new((7, 5));
+        let mut b = NativeTensor::new((5, 6));
+        let mut c = NativeTensor::new((7, 6));
+
+        bac32.fill_random(&mut a32, 0.0, 1.0);
+        bac32.fill_random(&mut b32, 0.0, 1.0);
+        bac.fill_random(&mut a, 0.0, 1.0);
+        bac.fill_random(&mut b, 0.0, 1.0);
+
+        bac32.matmul(&mut c32, &a32, &b32);
+        bac.matmul(&mut c, &a, &b);
+
+        for (x, y) in c32.read().iter().zip(c.read().iter()) {
+            assert!((*x as f64 - y).abs() < 1e-4, "{} != {}", x, y);
+        }
+
+        // reductions keep the tensor precision
+        let mut t = NativeTensor::new((2, ));
+        t.write().copy_from_slice(&[3.0, 4.0 + 1e-9]);
+
+        assert_eq!(bac.sum(&t), 7.0 + 1e-9);
+        assert!((bac.norm_l2(&t) - (5.0 + 0.8e-9)).abs() < 1e-15);
+
+        // L = sum(dy * y) checked against central differences, far tighter than f32 allows
+        let check = |name: &str, vals: &mut [f64], grads: &[f64], objective: &dyn Fn(&[f64]) -> f64| {
+            let h = 1e-5;
+
+            for i in 0..vals.len() {
+                let val = vals[i];
+
+                vals[i] = val + h;
+                let lp = objective(vals);
+                vals[i] = val - h;
+                let lm = objective(vals);
+                vals[i] = val;
+
+                let numeric = (lp - lm) / (2.0 * h);
+
+                assert!((numeric - grads[i]).abs() < 1e-8, "{}[{}]: {} != {}", name, i, grads[i], numeric);
+            }
+        };
+
+        let sum_mul = |a: &[f64], b: &[f64]| -> f64 { a.iter().zip(b.iter()).map(|(a, b)| a * b).sum() };
+
+        let mut x = NativeTensor::new((2, 3));
+        let mut y = NativeTensor::new((2, 3));
+        let mut dy = NativeTensor::new((2, 3));
+        let mut dx = NativeTensor::new((2, 3));
+
+        bac.load_tensor_f32(&mut x, &[-2.5, -0.7, -0.1, 0.2, 0.9, 3.0]);
+        bac.load_tensor_f32(&mut dy, &[0.3, -0.7, 1.1, -0.2, 0.9, 0.4]);
+
+        for &act in [Activation::Sigmoid, Activation::Tanh, Activation::Gelu, Activation::Swish, Activation::Softplus].iter() {
+            bac.activation(act, &mut y, &x);
+            bac.activation_grad(act, &mut dx, &x, &y, &dy);
+
+            check(&format!("{:?}", act), &mut x.read().to_vec(), dx.read(), &|vals| {
+                let mut x = NativeTensor::new((2, 3));
+                let mut y = NativeTensor::new((2, 3));
+
+                x.write().copy_from_slice(vals);
+                bac.activation(act, &mut y, &x);
+
+                sum_mul(y.read(), dy.read())
+            });
+        }
+
+        let conv_info = Conv2dInfo { padding: PaddingKind::Same, kernel: (3, 3), strides: (2, 1) };
+        let (rows, cols) = conv_info.output_size(5, 4);
+
+        let mut x = NativeTensor::new((2, 2, 5, 4));
+        let mut w = NativeTensor::new((3, 2, 3, 3));
+        let mut dy = NativeTensor::new((2, 3, rows, cols));
+        let mut dx = NativeTensor::new((2, 2, 5, 4));
+        let mut dw = NativeTensor::new((3, 2, 3, 3));
+
+        bac.fill_random(&mut x, 0.0, 1.0);
+        bac.fill_random(&mut w, 0.5, 1.0);
+        bac.fill_random(&mut dy, -0.5, 1.0);
+
+        bac.conv2d_backward_input(&mut (), &mut dx, &dy, &w, &conv_info);
+        bac.conv2d_backward_filter(&mut (), &mut dw, &x, &dy, &conv_info);
+
+        let conv = |x_vals: &[f64], w_vals: &[f64]| -> f64 {
+            let mut x = NativeTensor::new((2, 2, 5, 4));
+            let mut w = NativeTensor::new((3, 2, 3, 3));
+            let mut y = NativeTensor::new((2, 3, rows, cols));
+
+            x.write().copy_from_slice(x_vals);
+            w.write().copy_from_slice(w_vals);
+            bac.conv2d_forward(&mut (), &mut y, &x, &w, &conv_info);
+
+            sum_mul(y.read(), dy.read())
+        };
+
+        check("conv2d dx", &mut x.read().to_vec(), dx.read(), &|vals| conv(vals, w.read()));
+        check("conv2d dw", &mut w.read().to_vec(), dw.read(), &|vals| conv(x.read(), vals));
+    }
+}
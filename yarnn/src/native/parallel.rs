@@ -0,0 +1,148 @@
+/// Unit of work handed to a `ThreadPool`
+pub type Job<'a> = Box<dyn FnOnce() + Send + 'a>;
+
+/// Executor the `Native` backend splits its kernels across, see `Native::with_thread_pool`.
+///
+/// Any pool able to run borrowing jobs to completion fits, e.g. for `rayon`:
+///
+/// ```ignore
+/// struct Rayon(rayon::ThreadPool);
+///
+/// impl ThreadPool for Rayon {
+///     fn threads(&self) -> usize {
+///         self.0.current_num_threads()
+///     }
+///
+///     fn execute<'a>(&self, jobs: Vec<Job<'a>>) {
+///         self.0.scope(|s| for job in jobs { s.spawn(move |_| job()) });
+///     }
+/// }
+/// ```
+pub trait ThreadPool: Send + Sync {
+    /// Number of jobs worth splitting a kernel into, usually the number of worker threads
+    fn threads(&self) -> usize;
+
+    /// Runs all `jobs`, possibly concurrently, and returns once every one of them has finished
+    fn execute<'a>(&self, jobs: Vec<Job<'a>>);
+}
+
+/// `ThreadPool` spawning scoped `std` threads on every call, the calling thread runs one of the jobs
+pub struct ScopedThreadPool {
+    threads: usize,
+}
+
+impl ScopedThreadPool {
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0);
+
+        Self { threads }
+    }
+
+    /// One thread per available core
+    pub fn available() -> Self {
+        Self::new(std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+    }
+}
+
+impl ThreadPool for ScopedThreadPool {
+    #[inline]
+    fn threads(&self) -> usize {
+        self.threads
+    }
+
+    fn execute<'a>(&self, jobs: Vec<Job<'a>>) {
+        let mut jobs = jobs.into_iter();
+        let first = jobs.next();
+
+        std::thread::scope(|s| {
+            for job in jobs {
+                s.spawn(job);
+            }
+
+            if let Some(job) = first {
+                job();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::*;
+    use crate::native::{Native, NativeTensor};
+    use crate::tensor::{Tensor, TensorShape};
+    use std::sync::Arc;
+
+    fn random<S: Into<TensorShape>>(bac: &Native<f32>, shape: S) -> NativeTensor<f32> {
+        let mut t = NativeTensor::new(shape);
+        bac.fill_random(&mut t, 0.0, 1.0);
+        t
+    }
+
+    /// Runs `f` on the single threaded and the pooled backends and requires bitwise equal results
+    fn check<S, F>(name: &str, shape: S, f: F)
+        where S: Into<TensorShape>,
+              F: Fn(&Native<f32>, &mut NativeTensor<f32>)
+    {
+        let shape = shape.into();
+        let single = Native::default();
+        let pooled = Native::with_thread_pool(Arc::new(ScopedThreadPool::new(3)));
+
+        let mut expected = NativeTensor::new(shape.clone());
+        let mut actual = NativeTensor::new(shape);
+
+        f(&single, &mut expected);
+        f(&pooled, &mut actual);
+
+        assert!(expected.read() == actual.read(), "{} differs", name);
+    }
+
+    #[test]
+    fn test_parallel() {
+        let bac: Native<f32> = Default::default();
+
+        let a = random(&bac, (37, 41));
+        let b = random(&bac, (41, 29));
+        let at = random(&bac, (41, 37));
+        let bt = random(&bac, (29, 41));
+
+        check("matmul", (37, 29), |bac, c| bac.matmul(c, &a, &b));
+        check("matmul_nt", (37, 29), |bac, c| bac.matmul_nt(c, &a, &bt));
+        check("matmul_tn", (37, 29), |bac, c| bac.matmul_tn(c, &at, &b));
+        check("matmul_tt", (37, 29), |bac, c| bac.matmul_tt(c, &at, &bt));
+
+        let x = random(&bac, (5, 3, 9, 8));
+        let biases = random(&bac, (8, ));
+        let channel_biases = random(&bac, (3, ));
+
+        check("bias_add", (5, 3, 9, 8), |bac, y| { bac.copy(y, &x); bac.bias_add(y, &biases) });
+        check("bias_grad", (8, ), |bac, db| bac.bias_grad(db, &x));
+        check("bias_add2d", (5, 3, 9, 8), |bac, y| { bac.copy(y, &x); bac.bias_add2d(y, &channel_biases) });
+        check("bias_grad2d", (3, ), |bac, db| bac.bias_grad2d(db, &x));
+
+        let infos = [
+            Conv2dInfo { padding: PaddingKind::Valid, strides: (1, 1), kernel: (3, 3) },
+            Conv2dInfo { padding: PaddingKind::Same, strides: (2, 2), kernel: (5, 5) },
+            Conv2dInfo { padding: PaddingKind::Full, strides: (1, 2), kernel: (2, 3) },
+        ];
+
+        for info in infos.iter() {
+            let (rows, cols) = info.output_size(9, 8);
+            let (k_rows, k_cols) = info.kernel;
+
+            let w = random(&bac, (4, 3, k_rows, k_cols));
+            let dy = random(&bac, (5, 4, rows, cols));
+            let pool_dy = random(&bac, (5, 3, rows, cols));
+
+            check("conv2d_forward", (5, 4, rows, cols), |bac, y| bac.conv2d_forward(&mut (), y, &x, &w, info));
+            check("conv2d_backward_input", (5, 3, 9, 8), |bac, dx| bac.conv2d_backward_input(&mut (), dx, &dy, &w, info));
+            check("conv2d_backward_filter", (4, 3, k_rows, k_cols), |bac, dw| bac.conv2d_backward_filter(&mut (), dw, &x, &dy, info));
+
+            check("max_pool2d", (5, 3, rows, cols), |bac, y| bac.max_pool2d(y, &x, info));
+            check("max_pool2d_backprop", (5, 3, 9, 8), |bac, dx| bac.max_pool2d_backprop(dx, &pool_dy, &x, info));
+            check("avg_pool2d", (5, 3, rows, cols), |bac, y| bac.avg_pool2d(y, &x, info));
+            check("avg_pool2d_backprop", (5, 3, 9, 8), |bac, dx| bac.avg_pool2d_backprop(dx, &pool_dy, &x, info));
+        }
+    }
+}
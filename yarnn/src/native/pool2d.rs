@@ -1,26 +1,28 @@
-pub fn maxpool2d(y: &mut [f32], x: &[f32],
+use super::NativeNumber;
+
+pub fn maxpool2d<N: NativeNumber>(y: &mut [N], x: &[N],
                  y_rows: isize, y_cols: isize, 
                  x_rows: isize, x_cols: isize, 
                  w_rows: isize, w_cols: isize, 
-                 s_row: isize, s_col: isize) {
+                 s_row: isize, s_col: isize,
+                 p_row: isize, p_col: isize) {
 
     let y = &mut y[0..(y_rows * y_cols) as usize];
     let x = &x[0..(x_rows * x_cols) as usize];
     
     for y_y in 0..y_rows {
         for y_x in 0..y_cols {
-            let mut xi = s_row * y_y * x_cols + s_col * y_x;
+            let row = s_row * y_y - p_row;
+            let col = s_col * y_x - p_col;
             
-            let mut max = core::f32::NEG_INFINITY;
-            for _ in 0..w_rows {
-                for w_x in 0..w_cols {
-                    let val = x[(xi + w_x) as usize];
+            let mut max = N::NEG_INFINITY;
+            for x_y in row.max(0)..(row + w_rows).min(x_rows) {
+                for x_x in col.max(0)..(col + w_cols).min(x_cols) {
+                    let val = x[(x_y * x_cols + x_x) as usize];
                     if val > max {
                         max = val;
                     }
                 }
-                
-                xi += x_cols;
             }
             
             y[(y_y * y_cols + y_x) as usize] = max;
@@ -28,11 +30,12 @@ pub fn maxpool2d(y: &mut [f32], x: &[f32],
     }
 }
 
-pub fn maxpool2d_backward(dx: &mut [f32], x: &[f32], dy: &[f32],
+pub fn maxpool2d_backward<N: NativeNumber>(dx: &mut [N], x: &[N], dy: &[N],
                        x_rows: isize, x_cols: isize,
                        y_rows: isize, y_cols: isize,
                        w_rows: isize, w_cols: isize, 
-                       s_row: isize, s_col: isize) 
+                       s_row: isize, s_col: isize,
+                       p_row: isize, p_col: isize) 
 {
     let dx = &mut dx[0..(x_rows * x_cols) as usize];
     let x = &x[0..(x_rows * x_cols) as usize];
@@ -40,88 +43,95 @@ pub fn maxpool2d_backward(dx: &mut [f32], x: &[f32], dy: &[f32],
     
     for dy_y in 0..y_rows {
         for dy_x in 0..y_cols {
-            let mut xi = s_row * dy_y * x_cols + s_col * dy_x;
+            let row = s_row * dy_y - p_row;
+            let col = s_col * dy_x - p_col;
+
+            let rows = row.max(0)..(row + w_rows).min(x_rows);
+            let cols = col.max(0)..(col + w_cols).min(x_cols);
+
+            if rows.is_empty() || cols.is_empty() {
+                continue;
+            }
 
-            let mut max = core::f32::NEG_INFINITY;
-            let mut max_idx = -1;
-            for _ in 0..w_rows {
-                for w_x in 0..w_cols {
-                    let idx = xi + w_x;
+            // windows of only -inf or NaN values pass the delta on to their first cell
+            let mut max = N::NEG_INFINITY;
+            let mut max_idx = rows.start * x_cols + cols.start;
+            for x_y in rows {
+                for x_x in cols.clone() {
+                    let idx = x_y * x_cols + x_x;
                     let val = x[idx as usize];
                     if val > max {
                         max = val;
                         max_idx = idx;
                     }
                 }
-                xi += x_cols;
             }
             
-            dx[max_idx as usize] = dy[(dy_y * y_cols + dy_x) as usize];
+            // windows may overlap, so a single input can be the max of several
+            dx[max_idx as usize] += dy[(dy_y * y_cols + dy_x) as usize];
         }
     }
 }
 
-#[allow(dead_code)]
-pub fn avgpool2d(y: &mut [f32], x: &[f32],
+pub fn avgpool2d<N: NativeNumber>(y: &mut [N], x: &[N],
                  y_rows: isize, y_cols: isize, 
                  x_rows: isize, x_cols: isize, 
                  w_rows: isize, w_cols: isize, 
-                 s_row: isize, s_col: isize) {
+                 s_row: isize, s_col: isize,
+                 p_row: isize, p_col: isize) {
     
-    let w_size = w_rows * w_cols;
-
     let y = &mut y[0..(y_rows * y_cols) as usize];
     let x = &x[0..(x_rows * x_cols) as usize];
     
     for y_y in 0..y_rows {
         for y_x in 0..y_cols {
-            let mut xi = s_row * y_y * x_cols + s_col * y_x;
+            let row = s_row * y_y - p_row;
+            let col = s_col * y_x - p_col;
             
-            let mut sum = 0.0;
+            let mut sum = N::ZERO;
+            let mut count = 0;
 
-            for _ in 0..w_rows {
-                for w_x in 0..w_cols {
-                    sum += x[(xi + w_x) as usize];
+            // padded cells are not counted towards the average
+            for x_y in row.max(0)..(row + w_rows).min(x_rows) {
+                for x_x in col.max(0)..(col + w_cols).min(x_cols) {
+                    sum += x[(x_y * x_cols + x_x) as usize];
+                    count += 1;
                 }
-                
-                xi += x_cols;
             }
             
-            y[(y_y * y_cols + y_x) as usize] = sum / w_size as f32;
+            y[(y_y * y_cols + y_x) as usize] = sum / N::from_i32(count);
         }
     }
 }
 
-#[allow(dead_code)]
-pub fn avgpool2d_backward(dx: &mut [f32], x: &[f32], dy: &[f32],
+pub fn avgpool2d_backward<N: NativeNumber>(dx: &mut [N], dy: &[N],
                           x_rows: isize, x_cols: isize,
                           y_rows: isize, y_cols: isize,
                           w_rows: isize, w_cols: isize, 
-                          s_row: isize, s_col: isize) 
+                          s_row: isize, s_col: isize,
+                          p_row: isize, p_col: isize) 
 {
     let dx = &mut dx[0..(x_rows * x_cols) as usize];
-    let x = &x[0..(x_rows * x_cols) as usize];
     let dy = &dy[0..(y_rows * y_cols) as usize];
     
     for dy_y in 0..y_rows {
         for dy_x in 0..y_cols {
-            let mut xi = s_row * dy_y * x_cols + s_col * dy_x;
+            let row = s_row * dy_y - p_row;
+            let col = s_col * dy_x - p_col;
 
-            let mut max = core::f32::NEG_INFINITY;
-            let mut max_idx = -1;
-            for _ in 0..w_rows {
-                for w_x in 0..w_cols {
-                    let idx = xi + w_x;
-                    let val = x[idx as usize];
-                    if val > max {
-                        max = val;
-                        max_idx = idx;
-                    }
+            let rows = row.max(0)..(row + w_rows).min(x_rows);
+            let cols = col.max(0)..(col + w_cols).min(x_cols);
+
+            // spread the delta evenly over the cells the average was taken of,
+            // windows may overlap when strides are smaller than the pool
+            let count = rows.len() * cols.len();
+            let grad = dy[(dy_y * y_cols + dy_x) as usize] / N::from_usize(count);
+
+            for x_y in rows {
+                for x_x in cols.clone() {
+                    dx[(x_y * x_cols + x_x) as usize] += grad;
                 }
-                xi += x_cols;
             }
-            
-            dx[max_idx as usize] = dy[(dy_y * y_cols + dy_x) as usize];
         }
     }
 }
@@ -148,7 +158,7 @@ mod tests {
             0.0, 0.0, 0.0,
         ];
         
-        maxpool2d(y, x, 3, 3, 6, 6, 2, 2, 2, 2);
+        maxpool2d(y, x, 3, 3, 6, 6, 2, 2, 2, 2, 0, 0);
 
         assert_eq!(y, &[
              8.0, 10.0, 12.0, 
@@ -183,7 +193,7 @@ mod tests {
             0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
         ];
         
-        maxpool2d_backward(dx, x, dy, 6, 6, 3, 3, 2, 2, 2, 2);
+        maxpool2d_backward(dx, x, dy, 6, 6, 3, 3, 2, 2, 2, 2, 0, 0);
 
         let tt: &[f32] = &[
             0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 
@@ -197,6 +207,24 @@ mod tests {
         assert_eq!(dx, tt);
     }
 
+    #[test]
+    fn test_maxpool2d_backward_non_finite() {
+        let x: &[f32] = &[
+            f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NAN, 1.0,
+            f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NAN, f32::NAN,
+        ];
+
+        let dy: &[f32] = &[1.0, 2.0];
+        let dx: &mut [f32] = &mut [0.0; 8];
+
+        maxpool2d_backward(dx, x, dy, 2, 4, 1, 2, 2, 2, 2, 2, 0, 0);
+
+        assert_eq!(dx, &[
+            1.0, 0.0, 0.0, 2.0,
+            0.0, 0.0, 0.0, 0.0,
+        ]);
+    }
+
     #[test]
     fn test_avgpool2d() {
         let x: &[f32] = &[
@@ -214,7 +242,7 @@ mod tests {
             0.0, 0.0, 0.0,
         ];
         
-        avgpool2d(y, x, 3, 3, 6, 6, 2, 2, 2, 2);
+        avgpool2d(y, x, 3, 3, 6, 6, 2, 2, 2, 2, 0, 0);
 
         assert_eq!(y, &[
               4.5,  6.5,  8.5, 
@@ -222,4 +250,88 @@ mod tests {
              28.5, 30.5, 32.5,
         ])
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pool2d_full_padding() {
+        let x: &[f32] = &[
+            1.0, 2.0,
+            3.0, 4.0,
+        ];
+
+        let y: &mut [f32] = &mut [0.0; 9];
+
+        maxpool2d(y, x, 3, 3, 2, 2, 2, 2, 1, 1, 1, 1);
+
+        assert_eq!(y, &[
+            1.0, 2.0, 2.0,
+            3.0, 4.0, 4.0,
+            3.0, 4.0, 4.0,
+        ]);
+
+        let dy: &[f32] = &[1.0; 9];
+        let dx: &mut [f32] = &mut [0.0; 4];
+
+        maxpool2d_backward(dx, x, dy, 2, 2, 3, 3, 2, 2, 1, 1, 1, 1);
+
+        assert_eq!(dx, &[
+            1.0, 2.0,
+            2.0, 4.0,
+        ]);
+
+        avgpool2d(y, x, 3, 3, 2, 2, 2, 2, 1, 1, 1, 1);
+
+        assert_eq!(y, &[
+            1.0, 1.5, 2.0,
+            2.0, 2.5, 3.0,
+            3.0, 3.5, 4.0,
+        ]);
+    }
+
+    #[test]
+    fn test_avgpool2d_backward() {
+        let x: &[f32] = &[
+            0.3, -1.2,  0.5,  0.8, -0.1,
+            1.1,  0.4, -0.7,  0.2,  0.6,
+           -0.2,  2.0,  0.9, -0.4,  1.3,
+            0.8, -0.5,  0.1,  0.7, -0.9,
+            0.5,  0.3, -0.6,  1.0,  0.2,
+        ];
+
+        // weights for a scalar objective L = sum(w * y)
+        let w: &[f32] = &[
+            0.1, -0.4, 0.7,
+            0.2,  0.5, -0.3,
+           -0.6,  0.3, 0.9,
+        ];
+
+        // 3x3 pools with stride 2 overlap, `Same` padding adds one cell on every side
+        for &(pad, y_size) in &[(0, 2), (1, 3)] {
+            let objective = |x: &[f32]| -> f64 {
+                let y = &mut [0.0; 9];
+
+                avgpool2d(y, x, y_size, y_size, 5, 5, 3, 3, 2, 2, pad, pad);
+
+                y.iter().zip(w.iter()).map(|(&a, &b)| (a * b) as f64).sum()
+            };
+
+            let dy = &w[0..(y_size * y_size) as usize];
+            let dx = &mut [0.0; 25];
+
+            avgpool2d_backward(dx, dy, 5, 5, y_size, y_size, 3, 3, 2, 2, pad, pad);
+
+            let h = 1e-2;
+
+            for i in 0..25 {
+                let mut xp = x.to_vec();
+                let mut xm = x.to_vec();
+
+                xp[i] += h;
+                xm[i] -= h;
+
+                let numeric = (objective(&xp) - objective(&xm)) / (2.0 * h as f64);
+
+                assert!((numeric - dx[i] as f64).abs() < 1e-3, "dx[{}]: {} != {}", i, dx[i], numeric);
+            }
+        }
+    }
+}
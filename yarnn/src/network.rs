@@ -1,42 +1,234 @@
-pub struct Network<N, B, O, L> 
+use crate::backend::{Backend, BackendReduce};
+use crate::layer::Layer;
+use crate::loss::Loss;
+use crate::metrics::ConfusionMatrix;
+use crate::model::{Model, DefaultModel};
+use crate::optimizer::Optimizer;
+use crate::tensor::{Tensor, TensorShape};
+
+use core::cell::RefCell;
+use core::marker::PhantomData;
+
+
+/// Owns everything needed to train a layer and run it over plain `f32` slices.
+/// Samples are laid out one after another, each of them in the layer input (or output) shape.
+pub struct Network<N, B, O, LS, L>
     where B: Backend<N>,
           O: Optimizer<N, B>,
           LS: Loss<N, B>,
-          L: AbstractLayer<N, B>
+          L: Layer<N, B, O>
 {
     backend: B,
     optimizer: O,
     loss: LS,
-    train_ctx: L::Context,
-    predict_ctx: RefCell<L::Context>,
-    layer: L,
+    model: RefCell<DefaultModel<N, B, O, L>>,
+    _m: PhantomData<fn(N)>,
 }
 
-impl<N, B, O, L>  Network<N, B, O, L> 
+impl<N, B, O, LS, L> Network<N, B, O, LS, L>
     where B: Backend<N>,
           O: Optimizer<N, B>,
-          L: AbstractLayer<N, B>
+          LS: Loss<N, B>,
+          L: Layer<N, B, O>
 {
     pub fn new(backend: B, optimizer: O, loss: LS, layer: L) -> Self {
         Self {
             backend,
             optimizer,
             loss,
-            layer,
-            train_ctx: L::Context::default(),
-            predict_ctx: RefCell::new(L::Context::default()),
+            model: RefCell::new(DefaultModel::new(layer)),
+            _m: Default::default(),
+        }
+    }
+
+    pub fn init(&mut self) {
+        self.model.get_mut().init(&self.backend);
+    }
+
+    #[inline]
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    #[inline]
+    pub fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    #[inline]
+    pub fn layer_mut(&mut self) -> &mut L {
+        self.model.get_mut().layer_mut()
+    }
+
+    /// Mean loss over all samples of `x` against targets `y`
+    pub fn evaluate(&self, x: &[f32], y: &[f32]) -> f32
+        where B: BackendReduce<N>
+    {
+        let mut model = self.model.borrow_mut();
+        let (input_shape, output_shape) = (model.layer().input_shape(), model.layer().output_shape());
+        let count = sample_count(x, &input_shape, y, &output_shape);
+
+        let inputs = self.load_batch(x, count, &input_shape);
+        let targets = self.load_batch(y, count, &output_shape);
+
+        model.evaluate(&self.backend, &self.loss, &inputs, &targets)
+    }
+
+    /// Classifies all samples of `x` and compares the predictions with `labels`
+    pub fn confusion_matrix<LB: Copy + Into<u32>>(&self, x: &[f32], labels: &[LB]) -> ConfusionMatrix {
+        let mut model = self.model.borrow_mut();
+        let input_shape = model.layer().input_shape();
+        let count = x.len() / input_shape.size();
+
+        assert_eq!(count, labels.len(), "labels don't match the number of input samples");
+
+        let inputs = self.load_batch(x, count, &input_shape);
+
+        ConfusionMatrix::from_tensor(&self.backend, model.predict(&self.backend, &inputs), labels)
+    }
+
+    /// Runs inference over all samples of `x`, `pred` receives the outputs
+    pub fn predict(&self, x: &[f32], pred: &mut [f32]) {
+        let mut model = self.model.borrow_mut();
+        let (input_shape, output_shape) = (model.layer().input_shape(), model.layer().output_shape());
+        let count = sample_count(x, &input_shape, pred, &output_shape);
+
+        let inputs = self.load_batch(x, count, &input_shape);
+
+        self.backend.store_tensor_f32(model.predict(&self.backend, &inputs), pred);
+    }
+
+    /// Trains over `x` with targets `y` for `epochs` passes, in batches of `batch` samples.
+    /// The last batch of an epoch may be smaller.
+    pub fn train(&mut self, x: &[f32], y: &[f32], batch: usize, epochs: usize) {
+        assert!(batch > 0, "batch size must be positive");
+
+        let model = self.model.get_mut();
+        let (input_shape, output_shape) = (model.layer().input_shape(), model.layer().output_shape());
+        let count = sample_count(x, &input_shape, y, &output_shape);
+
+        let (input_size, output_size) = (input_shape.size(), output_shape.size());
+        let mut inputs = B::Tensor::new(batch_shape(batch, &input_shape));
+        let mut targets = B::Tensor::new(batch_shape(batch, &output_shape));
+
+        for _ in 0 .. epochs {
+            for offset in (0 .. count).step_by(batch) {
+                let bs = batch.min(count - offset);
+
+                if inputs.shape().get(0) as usize != bs {
+                    inputs.resize(batch_shape(bs, &input_shape));
+                    targets.resize(batch_shape(bs, &output_shape));
+                }
+
+                self.backend.load_tensor_f32(&mut inputs, &x[offset * input_size .. (offset + bs) * input_size]);
+                self.backend.load_tensor_f32(&mut targets, &y[offset * output_size .. (offset + bs) * output_size]);
+
+                model.train(&self.backend, &self.optimizer, &self.loss, &inputs, &targets);
+            }
         }
     }
 
-    fn evaluate(&self, X: &[f32], y: &[f32]) {
+    fn load_batch(&self, data: &[f32], count: usize, shape: &TensorShape) -> B::Tensor {
+        let mut tensor = B::Tensor::new(batch_shape(count, shape));
+        self.backend.load_tensor_f32(&mut tensor, data);
 
+        tensor
     }
+}
+
+fn batch_shape(bs: usize, shape: &TensorShape) -> TensorShape {
+    let mut batch_shape = TensorShape::new1d(bs as u32);
+    batch_shape.append(shape);
+
+    batch_shape
+}
+
+fn sample_count(x: &[f32], input_shape: &TensorShape, y: &[f32], output_shape: &TensorShape) -> usize {
+    let count = x.len() / input_shape.size();
+
+    assert_eq!(x.len(), count * input_shape.size(), "inputs are not a whole number of samples");
+    assert_eq!(y.len(), count * output_shape.size(), "outputs don't match the number of input samples");
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerExt;
+    use crate::layers::{Linear, LinearConfig};
+    use crate::losses::MeanSquareErrorLoss;
+    use crate::native::Native;
+    use crate::optimizer::LearningRate;
+    use crate::optimizers::{Sgd, Scheduled, StepDecay};
+
+    type B = Native<f32>;
+    type O = Sgd<f32, B>;
+
+    #[test]
+    fn test_network_train() {
+        let layer = Linear::<f32, B, O>::create(TensorShape::new1d(2), LinearConfig { units: 1, biases: true });
+        let mut net = Network::new(Native::default(), Sgd::new(0.1, 0.0, false), MeanSquareErrorLoss::new(), layer);
+
+        net.init();
+
+        // y = 2 * a - b + 0.5
+        let x = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.5, -1.0];
+        let y = [0.5, 2.5, -0.5, 1.5, 2.5];
 
-    fn predict(&self, X: &[f32], pred: &mut [f32]) {
+        let before = net.evaluate(&x, &y);
+        net.train(&x, &y, 2, 500);
+        let after = net.evaluate(&x, &y);
 
+        assert!(after < before);
+        assert!(after < 1e-4, "loss {}", after);
+
+        let mut pred = [0.0; 2];
+        net.predict(&[2.0, 1.0, -1.0, 0.0], &mut pred);
+
+        assert!((pred[0] - 3.5).abs() < 0.05, "{:?}", pred);
+        assert!((pred[1] + 1.5).abs() < 0.05, "{:?}", pred);
     }
 
-    fn train(&mut self, X: &[f32], y: &[f32], batch: usize, epochs: usize) {
+    #[test]
+    fn test_network_train_f64() {
+        type B = Native<f64>;
+        type O = Sgd<f64, B>;
+
+        let layer = Linear::<f64, B, O>::create(TensorShape::new1d(2), LinearConfig { units: 1, biases: true });
+        let mut net = Network::new(Native::default(), Sgd::new(0.1, 0.0, false), MeanSquareErrorLoss::new(), layer);
+
+        net.init();
 
+        let x = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.5, -1.0];
+        let y = [0.5, 2.5, -0.5, 1.5, 2.5];
+
+        net.train(&x, &y, 2, 2000);
+
+        let after = net.evaluate(&x, &y);
+        assert!(after < 1e-10, "loss {}", after);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_network_scheduled() {
+        type O = Scheduled<f32, B, Sgd<f32, B>, StepDecay>;
+
+        let layer = Linear::<f32, B, O>::create(TensorShape::new1d(2), LinearConfig { units: 1, biases: true });
+        let optimizer = Scheduled::new(Sgd::new(0.1, 0.0, false), StepDecay::new(1, 0.5));
+        let mut net = Network::new(Native::default(), optimizer, MeanSquareErrorLoss::new(), layer);
+
+        net.init();
+
+        let x = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        let y = [0.5, 2.5, -0.5];
+
+        // one epoch per `train` call, the schedule advances in between
+        for _ in 0 .. 3 {
+            net.train(&x, &y, 3, 1);
+            net.optimizer().step();
+        }
+
+        assert_eq!(net.optimizer().epoch(), 3);
+        assert!((net.optimizer().learning_rate() - 0.0125).abs() < 1e-7);
+    }
+}
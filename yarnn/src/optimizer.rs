@@ -6,10 +6,53 @@ pub trait OptimizerContext {
     fn new<S: Into<TensorShape>>(shape: S) -> Self;
 }
 
+/// Walks over optimizer state, see `Optimizer::visit_context` and `Optimizer::visit_state`
+pub trait StateVisitor<N, B: Backend<N>> {
+    fn visit_tensor(&mut self, name: &str, tensor: &mut B::Tensor);
+    fn visit_scalar(&mut self, name: &str, value: &mut f32);
+}
+
+/// All gradients of a model, see `Optimizer::prepare` and `params::LayerGrads`
+pub trait Gradients<N, B: Backend<N>> {
+    fn for_each_grad(&mut self, f: &mut dyn FnMut(&mut B::Tensor));
+}
+
+/// Optimizers with an adjustable learning rate, see `optimizers::Scheduled`
+pub trait LearningRate {
+    fn learning_rate(&self) -> f32;
+
+    /// Changes the learning rate of the following updates
+    fn set_learning_rate(&self, learning_rate: f32);
+}
+
+impl <'a, O: LearningRate> LearningRate for &'a O {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        (**self).learning_rate()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        (**self).set_learning_rate(learning_rate)
+    }
+}
+
 pub trait Optimizer<N, B: Backend<N>> {
     type Context: OptimizerContext;
 
     fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor);
+
+    /// Called once per training step, after `calc_gradients` and before `optimize`,
+    /// for optimizers which need to see all gradients before updating any parameter
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, _backend: &B, _grads: &mut G) {}
+
+    /// Passes every tensor of `ctx` to `visitor`, in a fixed order
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V);
+
+    /// Passes the optimizer own counters to `visitor`
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, _visitor: &mut V) {}
 }
 
 impl <'a, N, B: Backend<N>, O: Optimizer<N, B>> Optimizer<N, B> for &'a O {
@@ -19,4 +62,19 @@ impl <'a, N, B: Backend<N>, O: Optimizer<N, B>> Optimizer<N, B> for &'a O {
     fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
         (**self).update_params(backend, ctx, params, grads)
     }
+
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        (**self).prepare(backend, grads)
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        (**self).visit_context(ctx, visitor)
+    }
+
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        (**self).visit_state(visitor)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::backend::{Backend, BackendAdaDelta};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
+use crate::tensor::{Tensor, TensorShape};
+use core::marker::PhantomData;
+use core::cell::Cell;
+
+
+pub struct AdaDeltaContext<N, B> 
+    where B: Backend<N>
+{
+    accum: B::Tensor,
+    delta_accum: B::Tensor,
+    _m: PhantomData<fn(N, B)>,
+}
+
+impl<N, B: Backend<N>> OptimizerContext for AdaDeltaContext<N, B> {
+    fn new<S: Into<TensorShape>>(shape: S) -> Self {
+        let shape = shape.into();
+
+        Self {
+            accum: B::Tensor::new(shape.clone()),
+            delta_accum: B::Tensor::new(shape),
+            _m: Default::default(),
+        }
+    }
+}
+
+pub struct AdaDelta<N, B: Backend<N>> {
+    learning_rate: Cell<f32>,
+    rho: f32,
+    epsilon: f32,
+    _m: PhantomData<fn(N, B)>,   
+}
+
+impl<N, B> Default for AdaDelta<N, B> 
+    where B: Backend<N>
+{
+    fn default() -> Self {
+        Self {
+            learning_rate: Cell::new(1.0),
+            rho: 0.95,
+            epsilon: 1e-6,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N>> AdaDelta<N, B> {
+    pub fn new(learning_rate: f32, rho: f32, epsilon: f32) -> Self {
+        Self {
+            learning_rate: Cell::new(learning_rate),
+            rho,
+            epsilon,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendAdaDelta<N>> Optimizer<N, B> for AdaDelta<N, B> {
+    type Context = AdaDeltaContext<N, B>;
+
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        // a = rho * a + (1 - rho) * g^2
+        backend.scale(&mut ctx.accum, backend.scalar_f32(self.rho));
+        backend.axpys(&mut ctx.accum, backend.scalar_f32(1.0 - self.rho), grads);
+
+        // u = sqrt(d_a + epsilon) / sqrt(a + epsilon) * g
+        // p = p - lr * u
+        // d_a = rho * d_a + (1 - rho) * u^2
+        backend.adadelta_p(params, backend.scalar_f32(-self.learning_rate.get()), grads, &ctx.accum, &mut ctx.delta_accum,
+            backend.scalar_f32(self.rho), backend.scalar_f32(self.epsilon));
+    }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("accum", &mut ctx.accum);
+        visitor.visit_tensor("delta_accum", &mut ctx.delta_accum);
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for AdaDelta<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::backend::{Backend, BackendAdam};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
+use crate::tensor::{Tensor, TensorShape};
+use core::marker::PhantomData;
+use core::cell::Cell;
+
+
+pub struct AdaGradContext<N, B> 
+    where B: Backend<N>
+{
+    accum: B::Tensor,
+    _m: PhantomData<fn(N, B)>,
+}
+
+impl<N, B: Backend<N>> OptimizerContext for AdaGradContext<N, B> {
+    fn new<S: Into<TensorShape>>(shape: S) -> Self {
+        Self {
+            accum: B::Tensor::new(shape),
+            _m: Default::default(),
+        }
+    }
+}
+
+pub struct AdaGrad<N, B: Backend<N>> {
+    learning_rate: Cell<f32>,
+    epsilon: Option<f32>,
+    _m: PhantomData<fn(N, B)>,   
+}
+
+impl<N, B> Default for AdaGrad<N, B> 
+    where B: Backend<N>
+{
+    fn default() -> Self {
+        Self {
+            learning_rate: Cell::new(0.01),
+            epsilon: None,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N>> AdaGrad<N, B> {
+    pub fn new(learning_rate: f32) -> Self {
+        Self {
+            learning_rate: Cell::new(learning_rate),
+            epsilon: None,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendAdam<N>> Optimizer<N, B> for AdaGrad<N, B> {
+    type Context = AdaGradContext<N, B>;
+
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        // a = a + g^2
+        backend.axpys(&mut ctx.accum, backend.scalar_f32(1.0), grads);
+
+        // p = p - lr * g / (sqrt(a) + epsilon)
+        backend.adam_p(params, backend.scalar_f32(-self.learning_rate.get()), grads, &ctx.accum, backend.scalar_f32(self.epsilon.unwrap_or(f32::EPSILON)));
+    }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("accum", &mut ctx.accum);
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for AdaGrad<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
+}
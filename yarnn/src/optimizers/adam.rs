@@ -1,5 +1,5 @@
 use crate::backend::{Backend, BackendAdam};
-use crate::optimizer::{Optimizer, OptimizerContext};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
 use crate::tensor::{Tensor, TensorShape};
 use core::marker::PhantomData;
 use core::cell::Cell;
@@ -11,6 +11,8 @@ pub struct AdamContext<N, B>
     moms: B::Tensor,
     vels: B::Tensor,
     vhats: B::Tensor,
+    /// Number of updates applied to the parameter, used for bias correction
+    step: u32,
     _m: PhantomData<fn(N, B)>,
 }
 
@@ -22,18 +24,18 @@ impl<N, B: Backend<N>> OptimizerContext for AdamContext<N, B> {
             moms: B::Tensor::new(shape.clone()),
             vels: B::Tensor::new(shape.clone()),
             vhats: B::Tensor::new(shape),
+            step: 0,
             _m: Default::default(),
         }
     }
 }
 
 pub struct Adam<N, B: Backend<N>> {
-    learning_rate: f32,
+    learning_rate: Cell<f32>,
     beta_1: f32,
     beta_2: f32,
     epsilon: Option<f32>,
     amsgrad: bool,
-    iteration: Cell<f32>,
     _m: PhantomData<fn(N, B)>,   
 }
 
@@ -42,12 +44,11 @@ impl<N, B> Default for Adam<N, B>
 {
     fn default() -> Self {
         Self {
-            learning_rate: 0.001,
+            learning_rate: Cell::new(0.001),
             beta_1: 0.9,
             beta_2: 0.999,
             epsilon: None,
             amsgrad: false,
-            iteration: Cell::new(0.0),
             _m: Default::default(),
         }
     }
@@ -56,12 +57,11 @@ impl<N, B> Default for Adam<N, B>
 impl<N, B: Backend<N>> Adam<N, B> {
     pub fn new(learning_rate: f32, beta_1: f32, beta_2: f32, amsgrad: bool) -> Self {
         Self {
-            learning_rate,
+            learning_rate: Cell::new(learning_rate),
             beta_1,
             beta_2,
             epsilon: None,
             amsgrad,
-            iteration: Cell::new(0.0),
             _m: Default::default(),
         }
     }
@@ -71,11 +71,10 @@ impl<N, B: Backend<N> + BackendAdam<N>> Optimizer<N, B> for Adam<N, B> {
     type Context = AdamContext<N, B>;
 
     fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
-        let iter = self.iteration.get();
-        let t = iter + 1.0;
-        self.iteration.set(iter + 0.25);
+        ctx.step += 1;
+        let t = ctx.step as f32;
 
-        let lr_t = self.learning_rate * ((1.0 - self.beta_2.powf(t)).sqrt() / (1.0 - self.beta_1.powf(t)));
+        let lr_t = self.learning_rate.get() * ((1.0 - self.beta_2.powf(t)).sqrt() / (1.0 - self.beta_1.powf(t)));
 
         // m_t = (self.beta_1 * m) + (1. - self.beta_1) * g;
         backend.scale(&mut ctx.moms, backend.scalar_f32(self.beta_1));
@@ -93,4 +92,146 @@ impl<N, B: Backend<N> + BackendAdam<N>> Optimizer<N, B> for Adam<N, B> {
             backend.adam_p(params, backend.scalar_f32(-lr_t), &ctx.moms, &ctx.vels, backend.scalar_f32(self.epsilon.unwrap_or(core::f32::EPSILON)));
         }
     }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("moms", &mut ctx.moms);
+        visitor.visit_tensor("vels", &mut ctx.vels);
+
+        if self.amsgrad {
+            visitor.visit_tensor("vhats", &mut ctx.vhats);
+        }
+
+        let mut step = ctx.step as f32;
+        visitor.visit_scalar("step", &mut step);
+        ctx.step = step as u32;
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for Adam<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
+}
+
+/// Adam with decoupled weight decay: parameters are shrunk by `learning_rate * weight_decay`
+/// directly instead of adding the L2 term to the gradients like `WeightDecay` does
+pub struct AdamW<N, B: Backend<N>> {
+    adam: Adam<N, B>,
+    weight_decay: f32,
+}
+
+impl<N, B> Default for AdamW<N, B> 
+    where B: Backend<N>
+{
+    fn default() -> Self {
+        Self {
+            adam: Default::default(),
+            weight_decay: 0.01,
+        }
+    }
+}
+
+impl<N, B: Backend<N>> AdamW<N, B> {
+    pub fn new(learning_rate: f32, beta_1: f32, beta_2: f32, weight_decay: f32, amsgrad: bool) -> Self {
+        Self {
+            adam: Adam::new(learning_rate, beta_1, beta_2, amsgrad),
+            weight_decay,
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendAdam<N>> Optimizer<N, B> for AdamW<N, B> {
+    type Context = AdamContext<N, B>;
+
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        // p = p - lr * weight_decay * p
+        backend.scale(params, backend.scalar_f32(1.0 - self.adam.learning_rate.get() * self.weight_decay));
+
+        self.adam.update_params(backend, ctx, params, grads);
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.adam.visit_context(ctx, visitor)
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for AdamW<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.adam.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.adam.learning_rate.set(learning_rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{Native, NativeTensor};
+
+    type B = Native<f32>;
+
+    /// Textbook Adam over a single parameter after `t` updates
+    fn reference_update(p: &mut [f32], m: &mut [f32], v: &mut [f32], g: &[f32], t: i32) {
+        let (lr, beta_1, beta_2, eps) = (0.001f32, 0.9f32, 0.999f32, f32::EPSILON);
+        let lr_t = lr * (1.0 - beta_2.powi(t)).sqrt() / (1.0 - beta_1.powi(t));
+
+        for i in 0 .. p.len() {
+            m[i] = beta_1 * m[i] + (1.0 - beta_1) * g[i];
+            v[i] = beta_2 * v[i] + (1.0 - beta_2) * g[i] * g[i];
+            p[i] -= lr_t * m[i] / (v[i].sqrt() + eps);
+        }
+    }
+
+    fn check_model(param_count: usize) {
+        let backend = B::default();
+        let adam = Adam::<f32, B>::default();
+
+        let init = [0.5, -0.25, 1.0];
+        let mut params: Vec<NativeTensor<f32>> = (0 .. param_count).map(|_| NativeTensor::new((3, ))).collect();
+        let mut grads: Vec<NativeTensor<f32>> = (0 .. param_count).map(|_| NativeTensor::new((3, ))).collect();
+        let mut ctxs: Vec<AdamContext<f32, B>> = (0 .. param_count).map(|_| AdamContext::new((3, ))).collect();
+        let mut expected = vec![(init.to_vec(), vec![0.0; 3], vec![0.0; 3]); param_count];
+
+        for p in params.iter_mut() {
+            backend.load_tensor_f32(p, &init);
+        }
+
+        for step in 1 ..= 5 {
+            for k in 0 .. param_count {
+                let g: Vec<f32> = (0 .. 3)
+                    .map(|i| ((i * 3 + k * 5 + step * 7) % 11) as f32 / 11.0 - 0.5)
+                    .collect();
+
+                backend.load_tensor_f32(&mut grads[k], &g);
+                adam.update_params(&backend, &mut ctxs[k], &mut params[k], &mut grads[k]);
+
+                let (p, m, v) = &mut expected[k];
+                reference_update(p, m, v, &g, step as i32);
+            }
+        }
+
+        for (param, (p, _, _)) in params.iter().zip(expected.iter()) {
+            for (a, b) in param.read().iter().zip(p.iter()) {
+                assert!((a - b).abs() < 1e-6, "{} params: {} != {}", param_count, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adam_step_count() {
+        for &param_count in &[1, 2, 3, 4, 6] {
+            check_model(param_count);
+        }
+    }
 }
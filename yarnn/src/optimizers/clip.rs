@@ -0,0 +1,298 @@
+use crate::backend::{Backend, BackendClamp, BackendReduce, BackendScale};
+use crate::optimizer::{Optimizer, LearningRate, StateVisitor, Gradients};
+use core::marker::PhantomData;
+use core::cell::Cell;
+
+
+/// Clamps every gradient element into `[-clip, clip]` before passing it to the inner optimizer
+pub struct ClipByValue<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    clip: f32,
+    optimizer: O,
+    _m: PhantomData<fn(N, B, O)>,
+}
+
+impl<N, B, O> ClipByValue<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    pub fn new(clip: f32, optimizer: O) -> Self {
+        assert!(clip > 0.0);
+
+        Self {
+            clip,
+            optimizer,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B, O> Optimizer<N, B> for ClipByValue<N, B, O>
+    where B: Backend<N> + BackendClamp<N>,
+          O: Optimizer<N, B>
+{
+    type Context = O::Context;
+
+    #[inline]
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        backend.clamp(grads, backend.scalar_f32(-self.clip), backend.scalar_f32(self.clip));
+
+        self.optimizer.update_params(backend, ctx, params, grads);
+    }
+
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        self.optimizer.prepare(backend, grads)
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.optimizer.visit_context(ctx, visitor)
+    }
+
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        self.optimizer.visit_state(visitor)
+    }
+}
+
+impl<N, B, O> LearningRate for ClipByValue<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate
+{
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.optimizer.learning_rate()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.optimizer.set_learning_rate(learning_rate)
+    }
+}
+
+/// Rescales every gradient tensor whose L2 norm exceeds `max_norm` down to `max_norm`
+pub struct ClipByNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    max_norm: f32,
+    optimizer: O,
+    _m: PhantomData<fn(N, B, O)>,
+}
+
+impl<N, B, O> ClipByNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    pub fn new(max_norm: f32, optimizer: O) -> Self {
+        assert!(max_norm > 0.0);
+
+        Self {
+            max_norm,
+            optimizer,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B, O> Optimizer<N, B> for ClipByNorm<N, B, O>
+    where B: Backend<N> + BackendReduce<N> + BackendScale<N>,
+          O: Optimizer<N, B>
+{
+    type Context = O::Context;
+
+    #[inline]
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        let norm = backend.scalar_to_f64(backend.norm_l2(grads));
+        let max_norm = self.max_norm as f64;
+
+        if norm > max_norm {
+            backend.scale(grads, backend.scalar_f64(max_norm / norm));
+        }
+
+        self.optimizer.update_params(backend, ctx, params, grads);
+    }
+
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        self.optimizer.prepare(backend, grads)
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.optimizer.visit_context(ctx, visitor)
+    }
+
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        self.optimizer.visit_state(visitor)
+    }
+}
+
+impl<N, B, O> LearningRate for ClipByNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate
+{
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.optimizer.learning_rate()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.optimizer.set_learning_rate(learning_rate)
+    }
+}
+
+/// Rescales all gradients together whenever their joint L2 norm exceeds `max_norm`.
+/// The norm is measured in `prepare`, which has to be called on every step before `optimize`
+/// (`DefaultModel::train` does that).
+pub struct ClipByGlobalNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    max_norm: f32,
+    optimizer: O,
+    factor: Cell<Option<f64>>,
+    _m: PhantomData<fn(N, B, O)>,
+}
+
+impl<N, B, O> ClipByGlobalNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B>
+{
+    pub fn new(max_norm: f32, optimizer: O) -> Self {
+        assert!(max_norm > 0.0);
+
+        Self {
+            max_norm,
+            optimizer,
+            factor: Cell::new(None),
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B, O> Optimizer<N, B> for ClipByGlobalNorm<N, B, O>
+    where B: Backend<N> + BackendReduce<N> + BackendScale<N>,
+          O: Optimizer<N, B>
+{
+    type Context = O::Context;
+
+    #[inline]
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        let factor = self.factor.get()
+            .expect("ClipByGlobalNorm::prepare has to be called before the parameters are updated");
+
+        if factor < 1.0 {
+            backend.scale(grads, backend.scalar_f64(factor));
+        }
+
+        self.optimizer.update_params(backend, ctx, params, grads);
+    }
+
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        let mut sum_sq = 0.0;
+
+        grads.for_each_grad(&mut |grad| {
+            let norm = backend.scalar_to_f64(backend.norm_l2(grad));
+
+            sum_sq += norm * norm;
+        });
+
+        let norm: f64 = sum_sq.sqrt();
+        let max_norm = self.max_norm as f64;
+
+        self.factor.set(Some(if norm > max_norm { max_norm / norm } else { 1.0 }));
+
+        self.optimizer.prepare(backend, grads);
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.optimizer.visit_context(ctx, visitor)
+    }
+
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        self.optimizer.visit_state(visitor)
+    }
+}
+
+impl<N, B, O> LearningRate for ClipByGlobalNorm<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate
+{
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.optimizer.learning_rate()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.optimizer.set_learning_rate(learning_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::{Native, NativeTensor};
+    use crate::optimizer::OptimizerContext;
+    use crate::tensor::Tensor;
+    use crate::optimizers::Sgd;
+
+    type B = Native<f32>;
+
+    struct Grads<'a>(&'a mut [NativeTensor<f32>]);
+
+    impl<'a> Gradients<f32, B> for Grads<'a> {
+        fn for_each_grad(&mut self, f: &mut dyn FnMut(&mut NativeTensor<f32>)) {
+            for grad in self.0.iter_mut() {
+                f(grad);
+            }
+        }
+    }
+
+    /// Applies `optimizer` to zero parameters, with plain Sgd at rate 1 the result is `-clip(grads)`
+    fn run<O: Optimizer<f32, B>>(optimizer: &O, grads: &[&[f32]]) -> Vec<Vec<f32>> {
+        let backend = B::default();
+        let mut tensors: Vec<NativeTensor<f32>> = grads.iter().map(|g| {
+            let mut t = NativeTensor::new((g.len() as u32, ));
+            backend.load_tensor_f32(&mut t, g);
+            t
+        }).collect();
+
+        optimizer.prepare(&backend, &mut Grads(&mut tensors));
+
+        tensors.iter_mut().map(|grad| {
+            let mut params = NativeTensor::new(grad.shape().clone());
+            let mut ctx = O::Context::new(grad.shape().clone());
+
+            backend.fill_scalar(&mut params, 0.0);
+            optimizer.update_params(&backend, &mut ctx, &mut params, grad);
+
+            params.read().iter().map(|x| -x).collect()
+        }).collect()
+    }
+
+    #[test]
+    fn test_clip() {
+        let sgd = || Sgd::<f32, B>::new(1.0, 0.0, false);
+        let grads: &[&[f32]] = &[&[3.0, -4.0], &[0.5, -0.2, 12.0]];
+
+        assert_eq!(run(&ClipByValue::new(1.0, sgd()), grads), vec![vec![1.0, -1.0], vec![0.5, -0.2, 1.0]]);
+
+        let res = run(&ClipByNorm::new(2.5, sgd()), grads);
+        assert_eq!(res[0], vec![1.5, -2.0]);
+        assert!((res[1][2] - 12.0 * 2.5 / (0.25f32 + 0.04 + 144.0).sqrt()).abs() < 1e-6);
+
+        // joint norm is 13.0
+        let grads: &[&[f32]] = &[&[3.0, -4.0], &[12.0]];
+        assert_eq!(run(&ClipByGlobalNorm::new(6.5, sgd()), grads), vec![vec![1.5, -2.0], vec![6.0]]);
+        assert_eq!(run(&ClipByGlobalNorm::new(13.0, sgd()), grads), vec![vec![3.0, -4.0], vec![12.0]]);
+    }
+}
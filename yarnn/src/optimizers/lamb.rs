@@ -0,0 +1,125 @@
+use crate::backend::{Backend, BackendLamb};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
+use crate::tensor::{Tensor, TensorShape};
+use core::marker::PhantomData;
+use core::cell::Cell;
+
+
+pub struct LambContext<N, B> 
+    where B: Backend<N>
+{
+    moms: B::Tensor,
+    vels: B::Tensor,
+    update: B::Tensor,
+    step: u32,
+    _m: PhantomData<fn(N, B)>,
+}
+
+impl<N, B: Backend<N>> OptimizerContext for LambContext<N, B> {
+    fn new<S: Into<TensorShape>>(shape: S) -> Self {
+        let shape = shape.into();
+
+        Self {
+            moms: B::Tensor::new(shape.clone()),
+            vels: B::Tensor::new(shape.clone()),
+            update: B::Tensor::new(shape),
+            step: 0,
+            _m: Default::default(),
+        }
+    }
+}
+
+/// Adam with decoupled weight decay and a per tensor trust ratio `||p|| / ||update||`
+pub struct Lamb<N, B: Backend<N>> {
+    learning_rate: Cell<f32>,
+    beta_1: f32,
+    beta_2: f32,
+    epsilon: f32,
+    weight_decay: f32,
+    _m: PhantomData<fn(N, B)>,   
+}
+
+impl<N, B> Default for Lamb<N, B> 
+    where B: Backend<N>
+{
+    fn default() -> Self {
+        Self {
+            learning_rate: Cell::new(0.001),
+            beta_1: 0.9,
+            beta_2: 0.999,
+            epsilon: 1e-6,
+            weight_decay: 0.01,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N>> Lamb<N, B> {
+    pub fn new(learning_rate: f32, beta_1: f32, beta_2: f32, weight_decay: f32) -> Self {
+        Self {
+            learning_rate: Cell::new(learning_rate),
+            beta_1,
+            beta_2,
+            epsilon: 1e-6,
+            weight_decay,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendLamb<N>> Optimizer<N, B> for Lamb<N, B> {
+    type Context = LambContext<N, B>;
+
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        ctx.step += 1;
+        let t = ctx.step as f32;
+
+        // m_t = beta_1 * m + (1 - beta_1) * g
+        backend.scale(&mut ctx.moms, backend.scalar_f32(self.beta_1));
+        backend.axpy(&mut ctx.moms, backend.scalar_f32(1.0 - self.beta_1), grads);
+
+        // v_t = beta_2 * v + (1 - beta_2) * g^2
+        backend.scale(&mut ctx.vels, backend.scalar_f32(self.beta_2));
+        backend.axpys(&mut ctx.vels, backend.scalar_f32(1.0 - self.beta_2), grads);
+
+        // u = (m_t / (1 - beta_1^t)) / (sqrt(v_t / (1 - beta_2^t)) + epsilon) + weight_decay * p
+        backend.lamb_update(&mut ctx.update, params, &ctx.moms, &ctx.vels,
+            backend.scalar_f32(1.0 / (1.0 - self.beta_1.powf(t))),
+            backend.scalar_f32(1.0 / (1.0 - self.beta_2.powf(t))),
+            backend.scalar_f32(self.epsilon),
+            backend.scalar_f32(self.weight_decay));
+
+        // p_t = p - lr * (||p|| / ||u||) * u
+        let params_norm = backend.scalar_to_f64(backend.norm_l2(params));
+        let update_norm = backend.scalar_to_f64(backend.norm_l2(&ctx.update));
+
+        let trust_ratio = if params_norm > 0.0 && update_norm > 0.0 {
+            params_norm / update_norm
+        } else {
+            1.0
+        };
+
+        backend.axpy(params, backend.scalar_f64(-self.learning_rate.get() as f64 * trust_ratio), &ctx.update);
+    }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("moms", &mut ctx.moms);
+        visitor.visit_tensor("vels", &mut ctx.vels);
+
+        let mut step = ctx.step as f32;
+        visitor.visit_scalar("step", &mut step);
+        ctx.step = step as u32;
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for Lamb<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
+}
@@ -1,13 +1,25 @@
 mod sgd;
 mod adam;
 mod rmsprop;
+mod adagrad;
+mod adadelta;
+mod nadam;
+mod lamb;
+mod schedulers;
+mod clip;
 
 pub use self::sgd::*;
 pub use self::adam::*;
 pub use self::rmsprop::*;
+pub use self::adagrad::*;
+pub use self::adadelta::*;
+pub use self::nadam::*;
+pub use self::lamb::*;
+pub use self::schedulers::*;
+pub use self::clip::*;
 
 use crate::backend::{Backend, BackendAxpys};
-use crate::optimizer::Optimizer;
+use crate::optimizer::{Optimizer, LearningRate, StateVisitor, Gradients};
 use core::marker::PhantomData;
 
 pub struct WeightDecay<N, B, O>
@@ -44,4 +56,173 @@ impl<N, B, O> Optimizer<N, B> for WeightDecay<N, B, O>
 
         self.optimizer.update_params(backend, ctx, params, grads);
     }
+
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        self.optimizer.prepare(backend, grads)
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.optimizer.visit_context(ctx, visitor)
+    }
+
+    #[inline]
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        self.optimizer.visit_state(visitor)
+    }
+}
+
+impl<N, B, O> LearningRate for WeightDecay<N, B, O>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate
+{
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.optimizer.learning_rate()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.optimizer.set_learning_rate(learning_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Backend;
+    use crate::native::{Native, NativeTensor};
+    use crate::optimizer::OptimizerContext;
+    use crate::tensor::Tensor;
+
+    type B = Native<f32>;
+
+    const INIT: [f32; 4] = [0.5, -0.25, 1.0, -1.5];
+
+    fn grads(step: usize) -> Vec<f32> {
+        (0 .. INIT.len())
+            .map(|i| ((i * 3 + step * 7) % 11) as f32 / 11.0 - 0.5)
+            .collect()
+    }
+
+    /// Runs `optimizer` for a few steps and compares the parameters with `reference`,
+    /// a scalar implementation called as `reference(params, grads, step)` with steps counted from 1
+    fn check_trace<O, R>(optimizer: O, mut reference: R)
+        where O: Optimizer<f32, B>,
+              R: FnMut(&mut [f32], &[f32], i32)
+    {
+        let backend = B::default();
+        let mut params = NativeTensor::new((INIT.len() as u32, ));
+        let mut grad = NativeTensor::new((INIT.len() as u32, ));
+        let mut ctx = O::Context::new((INIT.len() as u32, ));
+        let mut expected = INIT.to_vec();
+
+        backend.load_tensor_f32(&mut params, &INIT);
+
+        for step in 1 ..= 8 {
+            let g = grads(step);
+
+            backend.load_tensor_f32(&mut grad, &g);
+            optimizer.update_params(&backend, &mut ctx, &mut params, &mut grad);
+
+            reference(&mut expected, &g, step as i32);
+
+            for (a, b) in params.read().iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 1e-5, "step {}: {} != {}", step, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adagrad() {
+        let (lr, eps) = (0.1, f32::EPSILON);
+        let mut accum = [0.0; 4];
+
+        check_trace(AdaGrad::new(lr), |p, g, _| {
+            for i in 0 .. p.len() {
+                accum[i] += g[i] * g[i];
+                p[i] -= lr * g[i] / (accum[i].sqrt() + eps);
+            }
+        });
+    }
+
+    #[test]
+    fn test_adadelta() {
+        let (rho, eps) = (0.95, 1e-6);
+        let (mut accum, mut delta_accum) = ([0.0; 4], [0.0f32; 4]);
+
+        check_trace(AdaDelta::default(), |p, g, _| {
+            for i in 0 .. p.len() {
+                accum[i] = rho * accum[i] + (1.0 - rho) * g[i] * g[i];
+
+                let delta = (delta_accum[i] + eps).sqrt() / (accum[i] + eps).sqrt() * g[i];
+
+                delta_accum[i] = rho * delta_accum[i] + (1.0 - rho) * delta * delta;
+                p[i] -= delta;
+            }
+        });
+    }
+
+    #[test]
+    fn test_adamw() {
+        let (lr, beta_1, beta_2, eps, wd) = (0.01f32, 0.9f32, 0.999f32, f32::EPSILON, 0.1);
+        let (mut m, mut v) = ([0.0; 4], [0.0; 4]);
+
+        check_trace(AdamW::new(lr, beta_1, beta_2, wd, false), |p, g, t| {
+            let lr_t = lr * (1.0 - beta_2.powi(t)).sqrt() / (1.0 - beta_1.powi(t));
+
+            for i in 0 .. p.len() {
+                m[i] = beta_1 * m[i] + (1.0 - beta_1) * g[i];
+                v[i] = beta_2 * v[i] + (1.0 - beta_2) * g[i] * g[i];
+                p[i] -= lr * wd * p[i] + lr_t * m[i] / (v[i].sqrt() + eps);
+            }
+        });
+    }
+
+    #[test]
+    fn test_nadam() {
+        let (lr, beta_1, beta_2, eps) = (0.01f32, 0.9f32, 0.999f32, f32::EPSILON);
+        let (mut m, mut v) = ([0.0; 4], [0.0; 4]);
+
+        check_trace(Nadam::new(lr, beta_1, beta_2), |p, g, t| {
+            for i in 0 .. p.len() {
+                m[i] = beta_1 * m[i] + (1.0 - beta_1) * g[i];
+                v[i] = beta_2 * v[i] + (1.0 - beta_2) * g[i] * g[i];
+
+                let m_hat = beta_1 * m[i] / (1.0 - beta_1.powi(t + 1)) + (1.0 - beta_1) * g[i] / (1.0 - beta_1.powi(t));
+                let v_hat = v[i] / (1.0 - beta_2.powi(t));
+
+                p[i] -= lr * m_hat / (v_hat.sqrt() + eps);
+            }
+        });
+    }
+
+    #[test]
+    fn test_lamb() {
+        let (lr, beta_1, beta_2, eps, wd) = (0.01f32, 0.9f32, 0.999f32, 1e-6, 0.01);
+        let (mut m, mut v) = ([0.0; 4], [0.0; 4]);
+
+        check_trace(Lamb::new(lr, beta_1, beta_2, wd), |p, g, t| {
+            let mut r = [0.0; 4];
+
+            for i in 0 .. p.len() {
+                m[i] = beta_1 * m[i] + (1.0 - beta_1) * g[i];
+                v[i] = beta_2 * v[i] + (1.0 - beta_2) * g[i] * g[i];
+
+                let m_hat = m[i] / (1.0 - beta_1.powi(t));
+                let v_hat = v[i] / (1.0 - beta_2.powi(t));
+
+                r[i] = m_hat / (v_hat.sqrt() + eps) + wd * p[i];
+            }
+
+            let p_norm = p.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let r_norm = r.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let trust_ratio = p_norm / r_norm;
+
+            for i in 0 .. p.len() {
+                p[i] -= lr * trust_ratio * r[i];
+            }
+        });
+    }
 }
@@ -0,0 +1,112 @@
+use crate::backend::{Backend, BackendNadam};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
+use crate::tensor::{Tensor, TensorShape};
+use core::marker::PhantomData;
+use core::cell::Cell;
+
+
+pub struct NadamContext<N, B> 
+    where B: Backend<N>
+{
+    moms: B::Tensor,
+    vels: B::Tensor,
+    step: u32,
+    _m: PhantomData<fn(N, B)>,
+}
+
+impl<N, B: Backend<N>> OptimizerContext for NadamContext<N, B> {
+    fn new<S: Into<TensorShape>>(shape: S) -> Self {
+        let shape = shape.into();
+
+        Self {
+            moms: B::Tensor::new(shape.clone()),
+            vels: B::Tensor::new(shape),
+            step: 0,
+            _m: Default::default(),
+        }
+    }
+}
+
+/// Adam with Nesterov momentum
+pub struct Nadam<N, B: Backend<N>> {
+    learning_rate: Cell<f32>,
+    beta_1: f32,
+    beta_2: f32,
+    epsilon: Option<f32>,
+    _m: PhantomData<fn(N, B)>,   
+}
+
+impl<N, B> Default for Nadam<N, B> 
+    where B: Backend<N>
+{
+    fn default() -> Self {
+        Self {
+            learning_rate: Cell::new(0.002),
+            beta_1: 0.9,
+            beta_2: 0.999,
+            epsilon: None,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N>> Nadam<N, B> {
+    pub fn new(learning_rate: f32, beta_1: f32, beta_2: f32) -> Self {
+        Self {
+            learning_rate: Cell::new(learning_rate),
+            beta_1,
+            beta_2,
+            epsilon: None,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<N, B: Backend<N> + BackendNadam<N>> Optimizer<N, B> for Nadam<N, B> {
+    type Context = NadamContext<N, B>;
+
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        ctx.step += 1;
+        let t = ctx.step as f32;
+
+        // m_t = beta_1 * m + (1 - beta_1) * g
+        backend.scale(&mut ctx.moms, backend.scalar_f32(self.beta_1));
+        backend.axpy(&mut ctx.moms, backend.scalar_f32(1.0 - self.beta_1), grads);
+
+        // v_t = beta_2 * v + (1 - beta_2) * g^2
+        backend.scale(&mut ctx.vels, backend.scalar_f32(self.beta_2));
+        backend.axpys(&mut ctx.vels, backend.scalar_f32(1.0 - self.beta_2), grads);
+
+        // m_hat = beta_1 * m_t / (1 - beta_1^(t + 1)) + (1 - beta_1) * g / (1 - beta_1^t)
+        let mom_coef = self.beta_1 / (1.0 - self.beta_1.powf(t + 1.0));
+        let grad_coef = (1.0 - self.beta_1) / (1.0 - self.beta_1.powf(t));
+
+        // p_t = p - lr * m_hat / (sqrt(v_t / (1 - beta_2^t)) + epsilon), the bias correction of v_t moved out of the root
+        let vel_corr = (1.0 - self.beta_2.powf(t)).sqrt();
+        let eps = self.epsilon.unwrap_or(f32::EPSILON);
+
+        backend.nadam_p(params, backend.scalar_f32(-self.learning_rate.get() * vel_corr), &ctx.moms, grads, &ctx.vels,
+            backend.scalar_f32(mom_coef), backend.scalar_f32(grad_coef), backend.scalar_f32(eps * vel_corr));
+    }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("moms", &mut ctx.moms);
+        visitor.visit_tensor("vels", &mut ctx.vels);
+
+        let mut step = ctx.step as f32;
+        visitor.visit_scalar("step", &mut step);
+        ctx.step = step as u32;
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for Nadam<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
+}
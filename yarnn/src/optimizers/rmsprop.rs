@@ -1,7 +1,8 @@
 use crate::backend::{Backend, BackendAdam};
-use crate::optimizer::{Optimizer, OptimizerContext};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
 use crate::tensor::{Tensor, TensorShape};
 use core::marker::PhantomData;
+use core::cell::Cell;
 
 
 pub struct RMSPropContext<N, B> 
@@ -23,7 +24,7 @@ impl<N, B: Backend<N>> OptimizerContext for RMSPropContext<N, B> {
 }
 
 pub struct RMSProp<N, B: Backend<N>> {
-    learning_rate: f32,
+    learning_rate: Cell<f32>,
     rho: f32,
     epsilon: Option<f32>,
     _m: PhantomData<fn(N, B)>,   
@@ -34,7 +35,7 @@ impl<N, B> Default for RMSProp<N, B>
 {
     fn default() -> Self {
         Self {
-            learning_rate: 0.001,
+            learning_rate: Cell::new(0.001),
             rho: 0.9,
             epsilon: None,
             _m: Default::default(),
@@ -45,7 +46,7 @@ impl<N, B> Default for RMSProp<N, B>
 impl<N, B: Backend<N>> RMSProp<N, B> {
     pub fn new(learning_rate: f32, rho: f32) -> Self {
         Self {
-            learning_rate,
+            learning_rate: Cell::new(learning_rate),
             rho,
             epsilon: None,
             _m: Default::default(),
@@ -62,6 +63,22 @@ impl<N, B: Backend<N> + BackendAdam<N>> Optimizer<N, B> for RMSProp<N, B> {
         backend.axpys(&mut ctx.accum, backend.scalar_f32(1.0 - self.rho), grads);
         
         // new_p = p - lr * g / (K.sqrt(new_a) + self.epsilon)
-        backend.adam_p(params, backend.scalar_f32(-self.learning_rate), &grads, &ctx.accum, backend.scalar_f32(self.epsilon.unwrap_or(core::f32::EPSILON)));
+        backend.adam_p(params, backend.scalar_f32(-self.learning_rate.get()), &grads, &ctx.accum, backend.scalar_f32(self.epsilon.unwrap_or(core::f32::EPSILON)));
+    }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("accum", &mut ctx.accum);
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for RMSProp<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
     }
 }
@@ -0,0 +1,357 @@
+use crate::backend::Backend;
+use crate::optimizer::{Optimizer, LearningRate, StateVisitor, Gradients};
+use core::marker::PhantomData;
+use core::cell::{Cell, RefCell};
+
+
+/// Learning rate policy over epochs, see `Scheduled`
+pub trait Scheduler {
+    /// Learning rate of `epoch` (counted from zero) for an optimizer created with rate `base`
+    fn learning_rate(&self, base: f32, epoch: u32) -> f32;
+
+    /// Receives the validation metric (lower is better) at the end of an epoch
+    #[inline]
+    fn observe(&mut self, _metric: f32) {}
+
+    /// Passes the state gathered by `observe` to `visitor`, so checkpoints of `Scheduled` resume the same schedule
+    #[inline]
+    fn visit_state<N, B: Backend<N>, V: StateVisitor<N, B>>(&mut self, _visitor: &mut V) {}
+}
+
+/// Multiplies the rate by `gamma` every `step_size` epochs
+pub struct StepDecay {
+    step_size: u32,
+    gamma: f32,
+}
+
+impl StepDecay {
+    pub fn new(step_size: u32, gamma: f32) -> Self {
+        assert!(step_size > 0);
+
+        Self { step_size, gamma }
+    }
+}
+
+impl Scheduler for StepDecay {
+    fn learning_rate(&self, base: f32, epoch: u32) -> f32 {
+        base * self.gamma.powi((epoch / self.step_size) as i32)
+    }
+}
+
+/// Multiplies the rate by `gamma` every epoch
+pub struct ExponentialDecay {
+    gamma: f32,
+}
+
+impl ExponentialDecay {
+    pub fn new(gamma: f32) -> Self {
+        Self { gamma }
+    }
+}
+
+impl Scheduler for ExponentialDecay {
+    fn learning_rate(&self, base: f32, epoch: u32) -> f32 {
+        base * self.gamma.powi(epoch as i32)
+    }
+}
+
+/// Anneals the rate from the base down to `min_lr` along a half cosine over `t_max` epochs
+pub struct CosineAnnealing {
+    t_max: u32,
+    min_lr: f32,
+}
+
+impl CosineAnnealing {
+    pub fn new(t_max: u32, min_lr: f32) -> Self {
+        assert!(t_max > 0);
+
+        Self { t_max, min_lr }
+    }
+}
+
+impl Scheduler for CosineAnnealing {
+    fn learning_rate(&self, base: f32, epoch: u32) -> f32 {
+        let t = epoch.min(self.t_max) as f32 / self.t_max as f32;
+
+        self.min_lr + (base - self.min_lr) * 0.5 * (1.0 + (core::f32::consts::PI * t).cos())
+    }
+}
+
+/// Grows the rate linearly up to the base over `warmup` epochs, then follows `inner`
+pub struct LinearWarmup<S: Scheduler> {
+    warmup: u32,
+    inner: S,
+}
+
+impl<S: Scheduler> LinearWarmup<S> {
+    pub fn new(warmup: u32, inner: S) -> Self {
+        Self { warmup, inner }
+    }
+}
+
+impl<S: Scheduler> Scheduler for LinearWarmup<S> {
+    fn learning_rate(&self, base: f32, epoch: u32) -> f32 {
+        if epoch < self.warmup {
+            base * (epoch + 1) as f32 / (self.warmup + 1) as f32
+        } else {
+            self.inner.learning_rate(base, epoch - self.warmup)
+        }
+    }
+
+    #[inline]
+    fn observe(&mut self, metric: f32) {
+        self.inner.observe(metric);
+    }
+
+    #[inline]
+    fn visit_state<N, B: Backend<N>, V: StateVisitor<N, B>>(&mut self, visitor: &mut V) {
+        self.inner.visit_state(visitor);
+    }
+}
+
+/// Multiplies the rate by `factor` whenever the observed metric hasn't improved for `patience` epochs
+pub struct ReduceOnPlateau {
+    factor: f32,
+    patience: u32,
+    min_lr: f32,
+    best: Option<f32>,
+    wait: u32,
+    reductions: u32,
+}
+
+impl ReduceOnPlateau {
+    pub fn new(factor: f32, patience: u32, min_lr: f32) -> Self {
+        Self {
+            factor,
+            patience,
+            min_lr,
+            best: None,
+            wait: 0,
+            reductions: 0,
+        }
+    }
+}
+
+impl Scheduler for ReduceOnPlateau {
+    fn learning_rate(&self, base: f32, _epoch: u32) -> f32 {
+        (base * self.factor.powi(self.reductions as i32)).max(self.min_lr)
+    }
+
+    fn observe(&mut self, metric: f32) {
+        match self.best {
+            Some(best) if metric >= best => {
+                self.wait += 1;
+
+                if self.wait > self.patience {
+                    self.reductions += 1;
+                    self.wait = 0;
+                }
+            },
+            _ => {
+                self.best = Some(metric);
+                self.wait = 0;
+            }
+        }
+    }
+
+    fn visit_state<N, B: Backend<N>, V: StateVisitor<N, B>>(&mut self, visitor: &mut V) {
+        // NaN while nothing has been observed
+        let mut best = self.best.unwrap_or(f32::NAN);
+        let mut wait = self.wait as f32;
+        let mut reductions = self.reductions as f32;
+
+        visitor.visit_scalar("best", &mut best);
+        visitor.visit_scalar("wait", &mut wait);
+        visitor.visit_scalar("reductions", &mut reductions);
+
+        self.best = if best.is_nan() { None } else { Some(best) };
+        self.wait = wait as u32;
+        self.reductions = reductions as u32;
+    }
+}
+
+/// Drives the learning rate of `optimizer` by `scheduler`, advanced by explicit `step` calls,
+/// usually once per epoch. The epoch counter, the base rate and the scheduler state are a part
+/// of the optimizer state in checkpoints.
+///
+/// `step` takes `&self`, so a `Network` can be trained one epoch at a time:
+///
+/// ```ignore
+/// for _ in 0 .. epochs {
+///     net.train(&x, &y, batch, 1);
+///     net.optimizer().step_with_metric(net.evaluate(&x_val, &y_val));
+/// }
+/// ```
+pub struct Scheduled<N, B, O, S>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          S: Scheduler
+{
+    optimizer: O,
+    scheduler: RefCell<S>,
+    base_lr: Cell<f32>,
+    epoch: Cell<u32>,
+    _m: PhantomData<fn(N, B)>,
+}
+
+impl<N, B, O, S> Scheduled<N, B, O, S>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate,
+          S: Scheduler
+{
+    pub fn new(optimizer: O, scheduler: S) -> Self {
+        let base_lr = optimizer.learning_rate();
+        let res = Self {
+            optimizer,
+            scheduler: RefCell::new(scheduler),
+            base_lr: Cell::new(base_lr),
+            epoch: Cell::new(0),
+            _m: Default::default(),
+        };
+
+        res.apply();
+        res
+    }
+
+    #[inline]
+    pub fn epoch(&self) -> u32 {
+        self.epoch.get()
+    }
+
+    #[inline]
+    pub fn inner(&self) -> &O {
+        &self.optimizer
+    }
+
+    /// Moves on to the next epoch
+    pub fn step(&self) {
+        self.epoch.set(self.epoch.get() + 1);
+        self.apply();
+    }
+
+    /// Moves on to the next epoch, passing the validation metric of the finished one to the scheduler
+    pub fn step_with_metric(&self, metric: f32) {
+        self.scheduler.borrow_mut().observe(metric);
+        self.step();
+    }
+
+    fn apply(&self) {
+        let learning_rate = self.scheduler.borrow().learning_rate(self.base_lr.get(), self.epoch.get());
+
+        self.optimizer.set_learning_rate(learning_rate);
+    }
+}
+
+impl<N, B, O, S> Optimizer<N, B> for Scheduled<N, B, O, S>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate,
+          S: Scheduler
+{
+    type Context = O::Context;
+
+    #[inline]
+    fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
+        self.optimizer.update_params(backend, ctx, params, grads);
+    }
+
+    #[inline]
+    fn prepare<G: Gradients<N, B>>(&self, backend: &B, grads: &mut G) {
+        self.optimizer.prepare(backend, grads)
+    }
+
+    #[inline]
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        self.optimizer.visit_context(ctx, visitor)
+    }
+
+    fn visit_state<V: StateVisitor<N, B>>(&self, visitor: &mut V) {
+        self.optimizer.visit_state(visitor);
+
+        let mut epoch = self.epoch.get() as f32;
+        visitor.visit_scalar("epoch", &mut epoch);
+        self.epoch.set(epoch as u32);
+
+        let mut base_lr = self.base_lr.get();
+        visitor.visit_scalar("base_lr", &mut base_lr);
+        self.base_lr.set(base_lr);
+
+        self.scheduler.borrow_mut().visit_state(visitor);
+
+        self.apply();
+    }
+}
+
+impl<N, B, O, S> LearningRate for Scheduled<N, B, O, S>
+    where B: Backend<N>,
+          O: Optimizer<N, B> + LearningRate,
+          S: Scheduler
+{
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.optimizer.learning_rate()
+    }
+
+    /// Replaces the base rate, the current rate becomes the scheduled one for it
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.base_lr.set(learning_rate);
+        self.apply();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::native::Native;
+    use crate::optimizers::Sgd;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_schedulers() {
+        let step = StepDecay::new(2, 0.5);
+        assert_close(step.learning_rate(1.0, 1), 1.0);
+        assert_close(step.learning_rate(1.0, 2), 0.5);
+        assert_close(step.learning_rate(1.0, 5), 0.25);
+
+        assert_close(ExponentialDecay::new(0.9).learning_rate(2.0, 2), 1.62);
+
+        let cosine = CosineAnnealing::new(4, 0.1);
+        assert_close(cosine.learning_rate(1.0, 0), 1.0);
+        assert_close(cosine.learning_rate(1.0, 2), 0.55);
+        assert_close(cosine.learning_rate(1.0, 4), 0.1);
+        assert_close(cosine.learning_rate(1.0, 9), 0.1);
+
+        let warmup = LinearWarmup::new(3, ExponentialDecay::new(0.5));
+        assert_close(warmup.learning_rate(1.0, 0), 0.25);
+        assert_close(warmup.learning_rate(1.0, 2), 0.75);
+        assert_close(warmup.learning_rate(1.0, 3), 1.0);
+        assert_close(warmup.learning_rate(1.0, 4), 0.5);
+
+        let mut plateau = ReduceOnPlateau::new(0.1, 1, 0.005);
+        for &metric in &[1.0, 0.5, 0.6, 0.7, 0.4, 0.4] {
+            plateau.observe(metric);
+        }
+        assert_close(plateau.learning_rate(1.0, 0), 0.1);
+        plateau.observe(0.4);
+        assert_close(plateau.learning_rate(1.0, 0), 0.01);
+        plateau.observe(0.4);
+        plateau.observe(0.4);
+        assert_close(plateau.learning_rate(1.0, 0), 0.005);
+    }
+
+    #[test]
+    fn test_scheduled() {
+        let optimizer = Scheduled::new(Sgd::<f32, Native<f32>>::new(0.1, 0.0, false), StepDecay::new(1, 0.5));
+
+        assert_close(optimizer.learning_rate(), 0.1);
+
+        optimizer.step();
+        optimizer.step();
+
+        assert_eq!(optimizer.epoch(), 2);
+        assert_close(optimizer.inner().learning_rate(), 0.025);
+    }
+}
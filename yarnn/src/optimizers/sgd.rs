@@ -1,7 +1,8 @@
 use crate::backend::{Backend, BackendScale, BackendAxpy, BackendAdd};
-use crate::optimizer::{Optimizer, OptimizerContext};
+use crate::optimizer::{Optimizer, LearningRate, OptimizerContext, StateVisitor};
 use crate::tensor::{Tensor, TensorShape};
 use core::marker::PhantomData;
+use core::cell::Cell;
 
 
 pub struct SgdContext<N, B> 
@@ -21,7 +22,7 @@ impl<N, B: Backend<N>> OptimizerContext for SgdContext<N, B> {
 }
 
 pub struct Sgd<N, B: Backend<N>> {
-    learning_rate: f32,
+    learning_rate: Cell<f32>,
     momentum: f32,
     nesterov: bool,
     _m: PhantomData<fn(N, B)>,   
@@ -32,7 +33,7 @@ impl<N, B> Default for Sgd<N, B>
 {
     fn default() -> Self {
         Self {
-            learning_rate: 0.01,
+            learning_rate: Cell::new(0.01),
             momentum: 0.0,
             nesterov: false,
             _m: Default::default(),
@@ -45,7 +46,7 @@ impl<N, B> Sgd<N, B>
 {
     pub fn new(learning_rate: f32, momentum: f32, nesterov: bool) -> Self {
         Self {
-            learning_rate,
+            learning_rate: Cell::new(learning_rate),
             momentum,
             nesterov,
             _m: Default::default(),
@@ -59,15 +60,31 @@ impl<N, B: Backend<N> + BackendScale<N> + BackendAxpy<N> + BackendAdd<N>> Optimi
     fn update_params(&self, backend: &B, ctx: &mut Self::Context, params: &mut B::Tensor, grads: &mut B::Tensor) {
         // m = momentum * m - lr * grads
         backend.scale(&mut ctx.moments, backend.scalar_f32(self.momentum));
-        backend.axpy(&mut ctx.moments, backend.scalar_f32(-self.learning_rate), grads);
+        backend.axpy(&mut ctx.moments, backend.scalar_f32(-self.learning_rate.get()), grads);
 
         if self.nesterov {
             // p += momentum * m - lr * grads
             backend.axpy(params, backend.scalar_f32(self.momentum), &ctx.moments);
-            backend.axpy(params, backend.scalar_f32(-self.learning_rate), grads);
+            backend.axpy(params, backend.scalar_f32(-self.learning_rate.get()), grads);
         } else {
             // p += m
             backend.add(params, &ctx.moments);
         }
     }
+
+    fn visit_context<V: StateVisitor<N, B>>(&self, ctx: &mut Self::Context, visitor: &mut V) {
+        visitor.visit_tensor("moments", &mut ctx.moments);
+    }
+}
+
+impl<N, B: Backend<N>> LearningRate for Sgd<N, B> {
+    #[inline]
+    fn learning_rate(&self) -> f32 {
+        self.learning_rate.get()
+    }
+
+    #[inline]
+    fn set_learning_rate(&self, learning_rate: f32) {
+        self.learning_rate.set(learning_rate);
+    }
 }
@@ -1,6 +1,8 @@
 use crate::backend::Backend;
-use crate::optimizer::{Optimizer, OptimizerContext};
+use crate::layer::Layer;
+use crate::optimizer::{Optimizer, OptimizerContext, Gradients};
 use crate::tensor::{TensorShape, Tensor};
+use core::marker::PhantomData;
 
 pub struct Params<N, B: Backend<N>, O: Optimizer<N, B>> {
     pub params: B::Tensor,
@@ -29,3 +31,59 @@ impl<N, B: Backend<N>, O: Optimizer<N, B>> Params<N, B, O> {
         backend.fill_scalar(&mut self.params, backend.scalar_f32(0.0));
     }
 }
+
+/// Walks over the state of a layer, see `Layer::visit_params`.
+/// `layer` and `name` identify the tensor within the layer, visiting order identifies the layer.
+pub trait ParamsVisitor<N, B: Backend<N>, O: Optimizer<N, B>> {
+    fn visit_params(&mut self, layer: &str, name: &str, params: &mut Params<N, B, O>);
+
+    /// Non trainable state, e.g. `BatchNorm` running statistics
+    fn visit_tensor(&mut self, layer: &str, name: &str, tensor: &mut B::Tensor);
+
+    /// Training progress, e.g. the `Dropout` step counter, only needed to resume training
+    #[inline]
+    fn visit_scalar(&mut self, _layer: &str, _name: &str, _value: &mut f32) {}
+}
+
+/// Exposes the gradients of every parameter of a layer, e.g. for `Optimizer::prepare`
+pub struct LayerGrads<'a, N, B, O, L>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>
+{
+    layer: &'a mut L,
+    _m: PhantomData<fn(N, B, O)>,
+}
+
+impl<'a, N, B, O, L> LayerGrads<'a, N, B, O, L>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>
+{
+    pub fn new(layer: &'a mut L) -> Self {
+        Self {
+            layer,
+            _m: Default::default(),
+        }
+    }
+}
+
+impl<'a, N, B, O, L> Gradients<N, B> for LayerGrads<'a, N, B, O, L>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>
+{
+    fn for_each_grad(&mut self, f: &mut dyn FnMut(&mut B::Tensor)) {
+        struct Visitor<'f, T>(&'f mut dyn FnMut(&mut T));
+
+        impl<'f, N, B: Backend<N>, O: Optimizer<N, B>> ParamsVisitor<N, B, O> for Visitor<'f, B::Tensor> {
+            fn visit_params(&mut self, _layer: &str, _name: &str, params: &mut Params<N, B, O>) {
+                (self.0)(&mut params.grads);
+            }
+
+            fn visit_tensor(&mut self, _layer: &str, _name: &str, _tensor: &mut B::Tensor) {}
+        }
+
+        self.layer.visit_params(&mut Visitor(f));
+    }
+}
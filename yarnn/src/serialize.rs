@@ -0,0 +1,696 @@
+//! Saving and loading of layer parameters and training checkpoints.
+//!
+//! Everything is little endian: the `YRNN` magic, a `u32` format version and a `u32`
+//! record count, followed by a record for every tensor visited by `Layer::visit_params`.
+//! A record holds the layer name and the tensor name (`u32` length prefixed UTF-8),
+//! the number of dims and the dims as `u32`s, and finally the `f32` data.
+//!
+//! Checkpoints additionally hold the optimizer context of every parameter right after
+//! its record (named `<param>.<tensor>`, e.g. `weights.moms`), the layer counters
+//! (e.g. the `Dropout` step) as single element records in visiting order, and the
+//! optimizer own counters as single element records of the `Optimizer` layer at the end.
+
+use crate::backend::Backend;
+use crate::layer::Layer;
+use crate::optimizer::{Optimizer, StateVisitor};
+use crate::params::{Params, ParamsVisitor};
+use crate::tensor::{Tensor, TensorShape};
+
+use core::fmt;
+use core::marker::PhantomData;
+
+pub const MAGIC: &[u8; 4] = b"YRNN";
+pub const VERSION: u32 = 1;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SerializeError {
+    BufferTooSmall,
+    UnexpectedEof,
+    BadMagic,
+    UnsupportedVersion(u32),
+    RecordCountMismatch { expected: u32, found: u32 },
+    NameMismatch { record: u32 },
+    ShapeMismatch { record: u32, expected: TensorShape, found: TensorShape },
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializeError::BufferTooSmall => write!(f, "buffer is too small"),
+            SerializeError::UnexpectedEof => write!(f, "unexpected end of data"),
+            SerializeError::BadMagic => write!(f, "not a yarnn weights file"),
+            SerializeError::UnsupportedVersion(version) => write!(f, "unsupported format version {}", version),
+            SerializeError::RecordCountMismatch { expected, found } =>
+                write!(f, "expected {} records, found {}", expected, found),
+            SerializeError::NameMismatch { record } =>
+                write!(f, "record {} belongs to a different layer", record),
+            SerializeError::ShapeMismatch { record, expected, found } =>
+                write!(f, "record {} has shape {}, expected {}", record, found, expected),
+        }
+    }
+}
+
+/// Saves the parameters of `layer`.
+/// Takes the layer mutably only because `ParamsVisitor` does, nothing is modified.
+pub fn save<N, B, O, L>(backend: &B, layer: &mut L) -> Vec<u8>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut buf = Vec::new();
+
+    save_to(backend, layer, None, &mut buf).expect("writing to a Vec can't fail");
+
+    buf
+}
+
+/// Saves the parameters of `layer` into `buf`, returns the number of bytes written.
+pub fn save_to_slice<N, B, O, L>(backend: &B, layer: &mut L, buf: &mut [u8]) -> Result<usize, SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut sink = SliceSink { buf, pos: 0 };
+
+    save_to(backend, layer, None, &mut sink)?;
+
+    Ok(sink.pos)
+}
+
+/// Loads parameters previously saved from a layer of the same architecture.
+/// On error the layer is left unchanged.
+pub fn load<N, B, O, L>(backend: &B, layer: &mut L, data: &[u8]) -> Result<(), SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    load_from(backend, layer, None, data)
+}
+
+/// Saves the parameters of `layer` together with the optimizer state, so training can be resumed.
+pub fn save_checkpoint<N, B, O, L>(backend: &B, layer: &mut L, optimizer: &O) -> Vec<u8>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut buf = Vec::new();
+
+    save_to(backend, layer, Some(optimizer), &mut buf).expect("writing to a Vec can't fail");
+
+    buf
+}
+
+/// Saves a checkpoint into `buf`, returns the number of bytes written.
+pub fn save_checkpoint_to_slice<N, B, O, L>(backend: &B, layer: &mut L, optimizer: &O, buf: &mut [u8]) -> Result<usize, SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut sink = SliceSink { buf, pos: 0 };
+
+    save_to(backend, layer, Some(optimizer), &mut sink)?;
+
+    Ok(sink.pos)
+}
+
+/// Restores the parameters of `layer` and the state of `optimizer` from a checkpoint.
+/// On error neither of them is changed.
+pub fn load_checkpoint<N, B, O, L>(backend: &B, layer: &mut L, optimizer: &O, data: &[u8]) -> Result<(), SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    load_from(backend, layer, Some(optimizer), data)
+}
+
+fn save_to<N, B, O, L, S>(backend: &B, layer: &mut L, optimizer: Option<&O>, sink: &mut S) -> Result<(), SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+          S: Sink,
+{
+    sink.put(MAGIC)?;
+    sink.put(&VERSION.to_le_bytes())?;
+    sink.put(&count_records(layer, optimizer).to_le_bytes())?;
+
+    let mut writer = Writer {
+        backend,
+        sink,
+        error: None,
+        _m: PhantomData,
+    };
+
+    walk(layer, optimizer, &mut writer);
+
+    match writer.error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn load_from<N, B, O, L>(backend: &B, layer: &mut L, optimizer: Option<&O>, data: &[u8]) -> Result<(), SerializeError>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut reader = Reader {
+        backend,
+        data,
+        pos: 0,
+        record: 0,
+        apply: false,
+        error: None,
+        _m: PhantomData,
+    };
+
+    if reader.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(SerializeError::BadMagic);
+    }
+
+    let version = reader.read_u32()?;
+    if version != VERSION {
+        return Err(SerializeError::UnsupportedVersion(version));
+    }
+
+    let found = reader.read_u32()?;
+    let expected = count_records(layer, optimizer);
+
+    if found != expected {
+        return Err(SerializeError::RecordCountMismatch { expected, found });
+    }
+
+    // the first pass only checks every record, so a bad file leaves the layer untouched
+    let start = reader.pos;
+    walk(layer, optimizer, &mut reader);
+
+    if let Some(err) = reader.error {
+        return Err(err);
+    }
+
+    reader.pos = start;
+    reader.record = 0;
+    reader.apply = true;
+    walk(layer, optimizer, &mut reader);
+
+    match reader.error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+fn count_records<N, B, O, L>(layer: &mut L, optimizer: Option<&O>) -> u32
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+{
+    let mut counter = Counter(0);
+
+    walk(layer, optimizer, &mut counter);
+
+    counter.0
+}
+
+/// Passes every record of `layer` (and of `optimizer`, if any) to `records`
+fn walk<N, B, O, L, R>(layer: &mut L, optimizer: Option<&O>, records: &mut R)
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          L: Layer<N, B, O>,
+          R: Records<N, B>,
+{
+    layer.visit_params(&mut LayerWalker { optimizer, records });
+
+    if let Some(optimizer) = optimizer {
+        optimizer.visit_state(&mut StateWalker { records, layer: "Optimizer", prefix: "" });
+    }
+}
+
+trait Records<N, B: Backend<N>> {
+    fn tensor(&mut self, layer: &str, name: &str, tensor: &mut B::Tensor);
+    fn scalar(&mut self, layer: &str, name: &str, value: &mut f32);
+}
+
+struct LayerWalker<'a, O, R> {
+    optimizer: Option<&'a O>,
+    records: &'a mut R,
+}
+
+impl<'a, N, B, O, R> ParamsVisitor<N, B, O> for LayerWalker<'a, O, R>
+    where B: Backend<N>,
+          O: Optimizer<N, B>,
+          R: Records<N, B>,
+{
+    fn visit_params(&mut self, layer: &str, name: &str, params: &mut Params<N, B, O>) {
+        self.records.tensor(layer, name, &mut params.params);
+
+        if let Some(optimizer) = self.optimizer {
+            optimizer.visit_context(&mut params.ctx, &mut StateWalker { records: &mut *self.records, layer, prefix: name });
+        }
+    }
+
+    fn visit_tensor(&mut self, layer: &str, name: &str, tensor: &mut B::Tensor) {
+        self.records.tensor(layer, name, tensor);
+    }
+
+    fn visit_scalar(&mut self, layer: &str, name: &str, value: &mut f32) {
+        if self.optimizer.is_some() {
+            self.records.scalar(layer, name, value);
+        }
+    }
+}
+
+struct StateWalker<'a, R> {
+    records: &'a mut R,
+    layer: &'a str,
+    prefix: &'a str,
+}
+
+impl<'a, R> StateWalker<'a, R> {
+    fn name(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.prefix, name)
+        }
+    }
+}
+
+impl<'a, N, B: Backend<N>, R: Records<N, B>> StateVisitor<N, B> for StateWalker<'a, R> {
+    fn visit_tensor(&mut self, name: &str, tensor: &mut B::Tensor) {
+        let name = self.name(name);
+
+        self.records.tensor(self.layer, &name, tensor);
+    }
+
+    fn visit_scalar(&mut self, name: &str, value: &mut f32) {
+        let name = self.name(name);
+
+        self.records.scalar(self.layer, &name, value);
+    }
+}
+
+trait Sink {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), SerializeError>;
+}
+
+impl Sink for Vec<u8> {
+    #[inline]
+    fn put(&mut self, bytes: &[u8]) -> Result<(), SerializeError> {
+        self.extend_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Sink for SliceSink<'a> {
+    #[inline]
+    fn put(&mut self, bytes: &[u8]) -> Result<(), SerializeError> {
+        let end = self.pos + bytes.len();
+
+        if end > self.buf.len() {
+            return Err(SerializeError::BufferTooSmall);
+        }
+
+        self.buf[self.pos .. end].copy_from_slice(bytes);
+        self.pos = end;
+
+        Ok(())
+    }
+}
+
+struct Counter(u32);
+
+impl<N, B: Backend<N>> Records<N, B> for Counter {
+    fn tensor(&mut self, _layer: &str, _name: &str, _tensor: &mut B::Tensor) {
+        self.0 += 1;
+    }
+
+    fn scalar(&mut self, _layer: &str, _name: &str, _value: &mut f32) {
+        self.0 += 1;
+    }
+}
+
+struct Writer<'a, N, B: Backend<N>, S> {
+    backend: &'a B,
+    sink: &'a mut S,
+    error: Option<SerializeError>,
+    _m: PhantomData<fn(N)>,
+}
+
+impl<'a, N, B: Backend<N>, S: Sink> Writer<'a, N, B, S> {
+    fn write(&mut self, layer: &str, name: &str, shape: &TensorShape, data: &[f32]) {
+        if self.error.is_none() {
+            self.error = self.try_write(layer, name, shape, data).err();
+        }
+    }
+
+    fn try_write(&mut self, layer: &str, name: &str, shape: &TensorShape, data: &[f32]) -> Result<(), SerializeError> {
+        self.put_str(layer)?;
+        self.put_str(name)?;
+
+        self.sink.put(&(shape.dims as u32).to_le_bytes())?;
+        for &dim in shape.as_slice() {
+            self.sink.put(&dim.to_le_bytes())?;
+        }
+
+        for val in data {
+            self.sink.put(&val.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn put_str(&mut self, s: &str) -> Result<(), SerializeError> {
+        self.sink.put(&(s.len() as u32).to_le_bytes())?;
+        self.sink.put(s.as_bytes())
+    }
+}
+
+impl<'a, N, B: Backend<N>, S: Sink> Records<N, B> for Writer<'a, N, B, S> {
+    fn tensor(&mut self, layer: &str, name: &str, tensor: &mut B::Tensor) {
+        let mut data = vec![0.0; tensor.shape().size()];
+        self.backend.store_tensor_f32(tensor, &mut data);
+
+        self.write(layer, name, tensor.shape(), &data);
+    }
+
+    fn scalar(&mut self, layer: &str, name: &str, value: &mut f32) {
+        self.write(layer, name, &TensorShape::new1d(1), &[*value]);
+    }
+}
+
+struct Reader<'a, N, B: Backend<N>> {
+    backend: &'a B,
+    data: &'a [u8],
+    pos: usize,
+    record: u32,
+    /// Values are only decoded and stored when set, otherwise records are just checked
+    apply: bool,
+    error: Option<SerializeError>,
+    _m: PhantomData<fn(N)>,
+}
+
+impl<'a, N, B: Backend<N>> Reader<'a, N, B> {
+    fn read(&mut self, layer: &str, name: &str, expected: &TensorShape) -> Option<Vec<f32>> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let res = self.try_read(layer, name, expected);
+        self.record += 1;
+
+        match res {
+            Ok(bytes) if self.apply => Some(bytes.chunks(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()),
+            Ok(_) => None,
+            Err(err) => {
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+
+    fn try_read(&mut self, layer: &str, name: &str, expected: &TensorShape) -> Result<&'a [u8], SerializeError> {
+        let record = self.record;
+
+        if self.read_str()? != layer.as_bytes() || self.read_str()? != name.as_bytes() {
+            return Err(SerializeError::NameMismatch { record });
+        }
+
+        let dims = self.read_u32()? as usize;
+        let mut found = TensorShape::zero();
+
+        for _ in 0 .. dims {
+            let dim = self.read_u32()?;
+            found.append(TensorShape::new1d(dim));
+        }
+
+        if &found != expected {
+            return Err(SerializeError::ShapeMismatch { record, expected: expected.clone(), found });
+        }
+
+        self.read_bytes(found.size() * 4)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SerializeError> {
+        let end = self.pos + len;
+
+        if end > self.data.len() {
+            return Err(SerializeError::UnexpectedEof);
+        }
+
+        let bytes = &self.data[self.pos .. end];
+        self.pos = end;
+
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, SerializeError> {
+        let b = self.read_bytes(4)?;
+
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_str(&mut self) -> Result<&'a [u8], SerializeError> {
+        let len = self.read_u32()? as usize;
+
+        self.read_bytes(len)
+    }
+}
+
+impl<'a, N, B: Backend<N>> Records<N, B> for Reader<'a, N, B> {
+    fn tensor(&mut self, layer: &str, name: &str, tensor: &mut B::Tensor) {
+        if let Some(data) = self.read(layer, name, tensor.shape()) {
+            self.backend.load_tensor_f32(tensor, &data);
+        }
+    }
+
+    fn scalar(&mut self, layer: &str, name: &str, value: &mut f32) {
+        if let Some(data) = self.read(layer, name, &TensorShape::new1d(1)) {
+            *value = data[0];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::LayerExt;
+    use crate::layers::{Linear, LinearConfig, BatchNorm, Dropout, DropoutConfig, Chain};
+    use crate::layer::LayerContext;
+    use crate::loss::Loss;
+    use crate::losses::MeanSquareErrorLoss;
+    use crate::native::{Native, NativeTensor};
+    use crate::optimizer::LearningRate;
+    use crate::optimizers::{Sgd, Adam, Scheduled, ReduceOnPlateau};
+
+    type B = Native<f32>;
+    type O = Sgd<f32, B>;
+    type Model<O> = Chain<f32, B, O, Linear<f32, B, O>, BatchNorm<f32, B, O>>;
+
+    fn model<O: Optimizer<f32, B>>(units: u32) -> Model<O> {
+        let linear = Linear::<f32, B, O>::create(TensorShape::new1d(4), LinearConfig { units, biases: true });
+
+        LayerExt::<f32, B, O>::add_layer::<BatchNorm<f32, B, O>>(linear, Default::default())
+    }
+
+    fn train<O: Optimizer<f32, B>, L: Layer<f32, B, O>>(backend: &B, model: &mut L, optimizer: &O, steps: usize) {
+        let loss = MeanSquareErrorLoss::new();
+        let mut ctx = Default::default();
+
+        let mut inputs = NativeTensor::new((2, 4));
+        let mut targets = NativeTensor::new((2, 3));
+        let mut deltas = NativeTensor::new((2, 3));
+
+        backend.load_tensor_f32(&mut inputs, &[0.5, -1.0, 0.25, 2.0, -0.5, 1.5, 1.0, 0.0]);
+        backend.load_tensor_f32(&mut targets, &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+
+        for _ in 0 .. steps {
+            model.forward(backend, &inputs, &mut ctx);
+            loss.derivative(backend, &mut deltas, ctx.outputs(), &targets);
+            model.backward(backend, &deltas, &inputs, &mut ctx);
+            model.calc_gradients(backend, &deltas, &inputs, &mut ctx);
+            model.optimize(backend, optimizer);
+        }
+    }
+
+    #[test]
+    fn test_save_load() {
+        let backend: B = Default::default();
+
+        let mut a = model::<O>(3);
+        let mut b = model::<O>(3);
+
+        a.init(&backend);
+
+        let data = save(&backend, &mut a);
+
+        assert_eq!(&data[0..4], MAGIC);
+
+        // flip a bit in the last float of the BatchNorm running variance
+        let mut other = data.clone();
+        let last = other.len() - 1;
+        other[last] ^= 1;
+
+        load(&backend, &mut b, &other).unwrap();
+        assert_eq!(other, save(&backend, &mut b));
+
+        load(&backend, &mut b, &data).unwrap();
+        assert_eq!(data, save(&backend, &mut b));
+
+        let mut buf = vec![0; data.len()];
+        assert_eq!(save_to_slice(&backend, &mut a, &mut buf), Ok(data.len()));
+        assert_eq!(buf, data);
+
+        assert_eq!(save_to_slice(&backend, &mut a, &mut buf[1..]), Err(SerializeError::BufferTooSmall));
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let backend: B = Default::default();
+
+        let mut a = model::<O>(3);
+        let mut b = model::<O>(5);
+
+        a.init(&backend);
+
+        let data = save(&backend, &mut a);
+
+        assert_eq!(load(&backend, &mut b, &data), Err(SerializeError::ShapeMismatch {
+            record: 0,
+            expected: TensorShape::new2d(4, 5),
+            found: TensorShape::new2d(4, 3),
+        }));
+
+        assert_eq!(load(&backend, &mut a, &data[0 .. data.len() - 1]), Err(SerializeError::UnexpectedEof));
+
+        let mut corrupted = data.clone();
+        corrupted[0] = b'X';
+        assert_eq!(load(&backend, &mut a, &corrupted), Err(SerializeError::BadMagic));
+
+        let mut corrupted = data.clone();
+        corrupted[4] = 2;
+        assert_eq!(load(&backend, &mut a, &corrupted), Err(SerializeError::UnsupportedVersion(2)));
+
+        // errors in the last records leave the earlier ones unloaded too
+        train(&backend, &mut a, &Sgd::new(0.1, 0.0, false), 1);
+        let trained = save(&backend, &mut a);
+
+        assert_eq!(load(&backend, &mut a, &data[0 .. data.len() - 1]), Err(SerializeError::UnexpectedEof));
+        assert_eq!(trained, save(&backend, &mut a));
+
+        let mut renamed = data.clone();
+        let pos = data.len() - 5 * 4 - "running_var".len();
+        renamed[pos] = b'R';
+        assert_eq!(load(&backend, &mut a, &renamed), Err(SerializeError::NameMismatch { record: 5 }));
+        assert_eq!(trained, save(&backend, &mut a));
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let backend: B = Default::default();
+
+        let mut full = model::<Adam<f32, B>>(3);
+        let full_optimizer = Adam::default();
+
+        full.init(&backend);
+        train(&backend, &mut full, &full_optimizer, 3);
+
+        let checkpoint = save_checkpoint(&backend, &mut full, &full_optimizer);
+
+        // weights only files don't carry the optimizer state
+        let weights = save(&backend, &mut full);
+        assert_eq!(load_checkpoint(&backend, &mut full, &full_optimizer, &weights),
+            Err(SerializeError::RecordCountMismatch { expected: 18, found: 6 }));
+
+        train(&backend, &mut full, &full_optimizer, 3);
+
+        let mut resumed = model::<Adam<f32, B>>(3);
+        let resumed_optimizer = Adam::default();
+
+        load_checkpoint(&backend, &mut resumed, &resumed_optimizer, &checkpoint).unwrap();
+        assert_eq!(checkpoint, save_checkpoint(&backend, &mut resumed, &resumed_optimizer));
+
+        train(&backend, &mut resumed, &resumed_optimizer, 3);
+
+        assert_eq!(save_checkpoint(&backend, &mut full, &full_optimizer),
+            save_checkpoint(&backend, &mut resumed, &resumed_optimizer));
+    }
+
+    #[test]
+    fn test_checkpoint_scheduler() {
+        type O = Scheduled<f32, B, Sgd<f32, B>, ReduceOnPlateau>;
+
+        let backend: B = Default::default();
+        let optimizer = || O::new(Sgd::new(0.1, 0.0, false), ReduceOnPlateau::new(0.5, 1, 0.0));
+
+        let mut full = model::<O>(3);
+        let full_optimizer = optimizer();
+
+        full.init(&backend);
+        train(&backend, &mut full, &full_optimizer, 1);
+        full_optimizer.step_with_metric(1.0);
+        full_optimizer.step_with_metric(2.0);
+
+        let checkpoint = save_checkpoint(&backend, &mut full, &full_optimizer);
+
+        let mut resumed = model::<O>(3);
+        let resumed_optimizer = optimizer();
+
+        load_checkpoint(&backend, &mut resumed, &resumed_optimizer, &checkpoint).unwrap();
+
+        // the second epoch without improvement halves the rate in both
+        full_optimizer.step_with_metric(2.0);
+        resumed_optimizer.step_with_metric(2.0);
+
+        assert_eq!(resumed_optimizer.epoch(), 3);
+        assert_eq!(full_optimizer.learning_rate(), 0.05);
+        assert_eq!(resumed_optimizer.learning_rate(), 0.05);
+
+        // a new base rate outlives the following steps
+        resumed_optimizer.set_learning_rate(0.2);
+        assert_eq!(resumed_optimizer.learning_rate(), 0.1);
+        resumed_optimizer.step();
+        assert_eq!(resumed_optimizer.learning_rate(), 0.1);
+    }
+
+    #[test]
+    fn test_checkpoint_dropout() {
+        type L = Chain<f32, B, O, Linear<f32, B, O>, Dropout<f32, B>>;
+
+        let backend: B = Default::default();
+        let optimizer = Sgd::new(0.1, 0.0, false);
+        let model = || -> L {
+            let linear = Linear::<f32, B, O>::create(TensorShape::new1d(4), LinearConfig { units: 3, biases: true });
+
+            LayerExt::<f32, B, O>::add_layer::<Dropout<f32, B>>(linear, DropoutConfig { rate: 0.5, seed: Some(7) })
+        };
+
+        let mut full = model();
+
+        full.init(&backend);
+        train(&backend, &mut full, &optimizer, 2);
+
+        // the weights alone don't carry the step, the checkpoint does
+        let weights = save(&backend, &mut full);
+        assert_eq!(load_checkpoint(&backend, &mut full, &optimizer, &weights),
+            Err(SerializeError::RecordCountMismatch { expected: 5, found: 2 }));
+
+        let checkpoint = save_checkpoint(&backend, &mut full, &optimizer);
+
+        let mut resumed = model();
+        load_checkpoint(&backend, &mut resumed, &optimizer, &checkpoint).unwrap();
+
+        // the same masks are drawn after resuming
+        train(&backend, &mut full, &optimizer, 2);
+        train(&backend, &mut resumed, &optimizer, 2);
+
+        assert_eq!(save_checkpoint(&backend, &mut full, &optimizer),
+            save_checkpoint(&backend, &mut resumed, &optimizer));
+    }
+}
@@ -41,7 +41,7 @@ impl<'a> ExactSizeIterator for TensorShapeIter<'a> {
     }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct TensorShape {
     shape: [u32; 4],
     pub dims: usize,